@@ -58,6 +58,10 @@ struct QueueShard<T> {
     trim: usize,
     /// The max number of values to keep in the shard.
     max: usize,
+    /// The number of [`Pool::get`] calls served by a pooled value.
+    hits: AtomicUsize,
+    /// The number of [`Pool::get`] calls that created a new value.
+    misses: AtomicUsize,
 }
 
 impl<T> QueueShard<T> {
@@ -67,10 +71,22 @@ impl<T> QueueShard<T> {
             elem_cnt: AtomicUsize::new(0),
             trim,
             max,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
         }
     }
 }
 
+/// Usage counters of a [`Pool`], see [`Pool::take_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// The number of values that were reused from the pool.
+    pub hits: usize,
+    /// The number of values that had to be created because the pool was
+    /// empty.
+    pub misses: usize,
+}
+
 /// A value borrowed from the [`Pool`] that can be dereferenced to `T`.
 #[derive(Debug)]
 pub struct Pooled<T: Default + Reuse + 'static> {
@@ -91,6 +107,7 @@ impl<T: Default + Reuse> Drop for Pooled<T> {
             elem_cnt,
             trim,
             max,
+            ..
         } = self.pool;
         if self.inner.reuse(*trim) {
             if elem_cnt.fetch_add(1, Ordering::Acquire) < *max {
@@ -139,9 +156,13 @@ impl<const S: usize, T: Default + Reuse> Pool<S, T> {
         let inner = match shard.queue.pop() {
             Some(el) => {
                 shard.elem_cnt.fetch_sub(1, Ordering::Relaxed);
+                shard.hits.fetch_add(1, Ordering::Relaxed);
                 el
             },
-            None => Default::default(),
+            None => {
+                shard.misses.fetch_add(1, Ordering::Relaxed);
+                Default::default()
+            },
         };
 
         Pooled { inner, pool: shard }
@@ -173,6 +194,17 @@ impl<const S: usize, T: Default + Reuse> Pool<S, T> {
         let shard = &self.queues[shard];
         Pooled { inner, pool: shard }
     }
+
+    /// Returns the number of hits and misses of [`Pool::get`] since the last
+    /// call to this method, and resets the counters.
+    pub fn take_stats(&self) -> PoolStats {
+        self.queues
+            .iter()
+            .fold(PoolStats::default(), |stats, shard| PoolStats {
+                hits: stats.hits + shard.hits.swap(0, Ordering::Relaxed),
+                misses: stats.misses + shard.misses.swap(0, Ordering::Relaxed),
+            })
+    }
 }
 
 impl<'a, const S: usize, T: Default + Extend<&'a u8> + Reuse> Pool<S, T> {
@@ -327,4 +359,22 @@ mod tests {
             assert_eq!(shard.elem_cnt.load(Ordering::Relaxed), MAX_IN_SHARD);
         }
     }
+
+    #[test]
+    fn test_stats() {
+        let pool = Box::leak(Box::new(Pool::<2, Vec<u8>>::new(4, 4)));
+
+        let bufs = (0..4)
+            .map(|_| pool.get_with(|b| b.push(0)))
+            .collect::<Vec<_>>();
+        assert_eq!(pool.take_stats(), PoolStats { hits: 0, misses: 4 });
+
+        drop(bufs);
+
+        let bufs = (0..6).map(|_| pool.get()).collect::<Vec<_>>();
+        assert_eq!(pool.take_stats(), PoolStats { hits: 4, misses: 2 });
+        assert_eq!(pool.take_stats(), PoolStats::default());
+
+        drop(bufs);
+    }
 }
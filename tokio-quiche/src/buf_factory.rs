@@ -41,6 +41,7 @@
 
 use buffer_pool::ConsumeBuffer;
 use buffer_pool::Pool;
+use buffer_pool::PoolStats;
 use buffer_pool::Pooled;
 use datagram_socket::MAX_DATAGRAM_SIZE;
 
@@ -145,6 +146,24 @@ impl BufFactory {
         dgram.extend(slice);
         dgram
     }
+
+    /// Returns the combined [`PoolStats`] of all built-in pools since the
+    /// last call, and resets them.
+    pub(crate) fn take_pool_stats() -> PoolStats {
+        [
+            &TINY_POOL,
+            &SMALL_POOL,
+            &MEDIUM_POOL,
+            &BUF_POOL,
+            &DATAGRAM_POOL,
+        ]
+        .into_iter()
+        .map(BufPool::take_stats)
+        .fold(PoolStats::default(), |total, stats| PoolStats {
+            hits: total.hits + stats.hits,
+            misses: total.misses + stats.misses,
+        })
+    }
 }
 
 #[cfg(feature = "zero-copy")]
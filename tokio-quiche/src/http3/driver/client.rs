@@ -139,6 +139,8 @@ struct PendingClientRequest {
 pub struct ClientHooks {
     /// Mapping from stream IDs to the associated [`PendingClientRequest`].
     pending_requests: BTreeMap<u64, PendingClientRequest>,
+    /// Tracks the number of requests that have been sent by this driver.
+    requests: u64,
 }
 
 impl ClientHooks {
@@ -187,6 +189,12 @@ impl ClientHooks {
             .pending_requests
             .insert(stream_id, PendingClientRequest { send, recv });
 
+        // Every request after the first reuses this QUIC connection
+        if driver.hooks.requests > 0 {
+            driver.pending_conn_metrics.connection_reuses += 1;
+        }
+        driver.hooks.requests += 1;
+
         // Notify the H3Controller that we've allocated a stream_id for a
         // given request_id.
         let _ = driver
@@ -240,6 +248,7 @@ impl DriverHooks for ClientHooks {
     fn new(_settings: &Http3Settings) -> Self {
         Self {
             pending_requests: BTreeMap::new(),
+            requests: 0,
         }
     }
 
@@ -39,6 +39,8 @@ use std::error::Error;
 use std::fmt;
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use datagram_socket::StreamClosureKind;
 use foundations::telemetry::log;
@@ -58,10 +60,13 @@ use tokio_util::sync::PollSender;
 
 use self::hooks::DriverHooks;
 use self::hooks::InboundHeaders;
+use self::streams::blocked_direction;
 use self::streams::FlowCtx;
 use self::streams::HaveUpstreamCapacity;
+use self::streams::OutboundHeadersProbe;
 use self::streams::ReceivedDownstreamData;
 use self::streams::StreamCtx;
+use self::streams::StreamMetricsEvent;
 use self::streams::StreamReady;
 use self::streams::WaitForDownstreamData;
 use self::streams::WaitForStream;
@@ -71,6 +76,7 @@ use crate::buf_factory::PooledBuf;
 use crate::buf_factory::PooledDgram;
 use crate::http3::settings::Http3Settings;
 use crate::http3::H3AuditStats;
+use crate::metrics::labels;
 use crate::metrics::Metrics;
 use crate::quic::HandshakeInfo;
 use crate::quic::QuicCommand;
@@ -106,6 +112,12 @@ const STREAM_CAPACITY: usize = 1; // Set to 1 to stress write_pending under test
 // to 3MB of max buffered data at 1500 bytes per datagram.
 const FLOW_CAPACITY: usize = 2048;
 
+// Identifier of the SETTINGS_MAX_FIELD_SECTION_SIZE setting (RFC 9114).
+const SETTINGS_MAX_FIELD_SECTION_SIZE: u64 = 0x6;
+
+// Interval between two samples of the body bytes queued on all streams.
+const PENDING_SEND_BYTES_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Used by a local task to send [`OutboundFrame`]s to a peer on the
 /// stream or flow associated with this channel.
 pub type OutboundFrameSender = PollSender<OutboundFrame>;
@@ -335,6 +347,16 @@ pub struct H3Driver<H: DriverHooks> {
     /// we need to send to it and its channel is at capacity, or if we need
     /// data from its channel and the channel is empty.
     waiting_streams: FuturesUnordered<WaitForStream>,
+    /// Stream lifecycle events to report in the next call to
+    /// `ApplicationOverQuic::report_metrics`.
+    pending_stream_metrics: Vec<StreamMetricsEvent>,
+    /// Request streams whose response was fully sent, with the time their
+    /// request was received. The request's total duration is reported once
+    /// the response is acknowledged.
+    unacked_responses: Vec<(u64, Instant)>,
+    /// Connection-level events to report in the next call to
+    /// `ApplicationOverQuic::report_metrics`.
+    pending_conn_metrics: PendingConnectionMetrics,
 
     /// Receives [`OutboundFrame`]s from all datagram flows on the connection.
     dgram_recv: OutboundFrameStream,
@@ -345,12 +367,93 @@ pub struct H3Driver<H: DriverHooks> {
     pooled_buf: PooledBuf,
     /// The maximum HTTP/3 stream ID seen on this connection.
     max_stream_seen: u64,
+    /// Number of HTTP/3 streams opened on this connection.
+    streams_opened: u64,
+    /// Bytes this connection currently contributes to the
+    /// `pending_send_data_bytes` gauge.
+    reported_pending_send_bytes: u64,
+    /// Time at which `reported_pending_send_bytes` was last sampled.
+    pending_send_bytes_sampled_at: Option<Instant>,
+    /// Time at which the handshake completed. Cleared once the first stream
+    /// is opened.
+    established_at: Option<Instant>,
 
     /// Tracks whether we have forwarded the HTTP/3 SETTINGS frame
     /// to the [H3Controller] once.
     settings_received_and_forwarded: bool,
 }
 
+/// Connection-level events which have not been reported to [`Metrics`] yet.
+#[derive(Default)]
+struct PendingConnectionMetrics {
+    /// Number of outbound datagrams dropped.
+    dgram_drops: u64,
+    /// Number of GOAWAY frames sent.
+    goaways_sent: u64,
+    /// Number of GOAWAY frames received.
+    goaways_received: u64,
+    /// Number of requests sent on a connection which already carried one.
+    connection_reuses: u64,
+    /// Whether our SETTINGS frame was queued.
+    settings_sent: bool,
+    /// Whether the peer's SETTINGS frame was received.
+    settings_received: bool,
+    /// The peer's `SETTINGS_MAX_FIELD_SECTION_SIZE`, if advertised.
+    peer_max_field_section_size: Option<u64>,
+    /// Number of streams written by each `process_writes` call.
+    write_batch_sizes: Vec<usize>,
+    /// Unidirectional streams opened by either endpoint.
+    uni_streams_opened: Vec<labels::H3StreamType>,
+    /// Time between the end of the handshake and the first stream.
+    first_stream_delay: Option<Duration>,
+}
+
+impl PendingConnectionMetrics {
+    fn report(self, metrics: &impl Metrics) {
+        if self.dgram_drops > 0 {
+            metrics.datagram_drop_count().inc_by(self.dgram_drops);
+        }
+        if self.goaways_sent > 0 {
+            metrics.h3_goaway_sent_count().inc_by(self.goaways_sent);
+        }
+        if self.goaways_received > 0 {
+            metrics
+                .h3_goaway_received_count()
+                .inc_by(self.goaways_received);
+        }
+        if self.connection_reuses > 0 {
+            metrics
+                .connection_reuse_count()
+                .inc_by(self.connection_reuses);
+        }
+        if self.settings_sent {
+            metrics.h3_settings_sent_count().inc();
+        }
+        if self.settings_received {
+            metrics.h3_settings_received_count().inc();
+        }
+        if let Some(size) = self.peer_max_field_section_size {
+            metrics
+                .h3_settings_max_field_section_size_histogram()
+                .observe(size as f64);
+        }
+        if !self.write_batch_sizes.is_empty() {
+            let histogram = metrics.write_batch_size();
+            for size in self.write_batch_sizes {
+                histogram.observe(size as f64);
+            }
+        }
+        for stream_type in self.uni_streams_opened {
+            metrics.h3_unidirectional_stream_opened(stream_type).inc();
+        }
+        if let Some(delay) = self.first_stream_delay {
+            metrics
+                .connection_age_at_first_stream()
+                .observe(delay.as_nanos() as u64);
+        }
+    }
+}
+
 impl<H: DriverHooks> H3Driver<H> {
     /// Builds a new [H3Driver] and an associated [H3Controller].
     ///
@@ -377,8 +480,15 @@ impl<H: DriverHooks> H3Driver<H> {
                 dgram_send: PollSender::new(dgram_send),
                 pooled_buf: BufFactory::get_max_buf(),
                 max_stream_seen: 0,
+                streams_opened: 0,
+                reported_pending_send_bytes: 0,
+                pending_send_bytes_sampled_at: None,
+                established_at: None,
 
                 waiting_streams: FuturesUnordered::new(),
+                pending_stream_metrics: Vec::new(),
+                unacked_responses: Vec::new(),
+                pending_conn_metrics: PendingConnectionMetrics::default(),
 
                 settings_received_and_forwarded: false,
             },
@@ -417,6 +527,26 @@ impl<H: DriverHooks> H3Driver<H> {
     fn insert_stream(&mut self, stream_id: u64, ctx: StreamCtx) {
         self.stream_map.insert(stream_id, ctx);
         self.max_stream_seen = self.max_stream_seen.max(stream_id);
+        self.streams_opened += 1;
+        self.pending_stream_metrics
+            .push(StreamMetricsEvent::Opened { stream_id });
+
+        if let Some(established_at) = self.established_at.take() {
+            self.pending_conn_metrics.first_stream_delay =
+                Some(established_at.elapsed());
+        }
+    }
+
+    /// Returns the number of body bytes the application handed to us which
+    /// quiche did not accept yet.
+    fn queued_body_bytes(&self) -> u64 {
+        self.stream_map
+            .values()
+            .map(|ctx| match &ctx.queued_frame {
+                Some(OutboundFrame::Body(body, _)) => body.as_ref().len() as u64,
+                _ => 0,
+            })
+            .sum()
     }
 
     /// Fetches body chunks from the [`quiche::h3::Connection`] and forwards
@@ -484,9 +614,19 @@ impl<H: DriverHooks> H3Driver<H> {
                     let _ = self.h3_event_sender.send(event.into());
 
                     permit.send(InboundFrame::Body(body, false));
+
+                    if let Some(since) = ctx.read_blocked_since.take() {
+                        self.pending_stream_metrics.push(
+                            StreamMetricsEvent::ReadUnblocked {
+                                blocked_for: since.elapsed(),
+                            },
+                        );
+                    }
+                },
+                Err(h3::Error::Done) => {
+                    ctx.read_blocked_since.get_or_insert_with(Instant::now);
+                    break StreamStatus::Done { close: false };
                 },
-                Err(h3::Error::Done) =>
-                    break StreamStatus::Done { close: false },
                 Err(_) => break StreamStatus::Done { close: true },
             }
         };
@@ -530,6 +670,9 @@ impl<H: DriverHooks> H3Driver<H> {
         ctx.fin_recv = true;
         ctx.audit_stats
             .set_recvd_stream_fin(StreamClosureKind::Explicit);
+        if qconn.is_server() {
+            ctx.request_completed_at = Some(Instant::now());
+        }
 
         // It's important to send this H3Event before process_h3_data so that
         // a server can (potentially) generate the control response before the
@@ -588,7 +731,10 @@ impl<H: DriverHooks> H3Driver<H> {
             },
 
             h3::Event::PriorityUpdate => Ok(()),
-            h3::Event::GoAway => Err(H3ConnectionError::GoAway),
+            h3::Event::GoAway => {
+                self.pending_conn_metrics.goaways_received += 1;
+                Err(H3ConnectionError::GoAway)
+            },
         }
     }
 
@@ -604,10 +750,22 @@ impl<H: DriverHooks> H3Driver<H> {
 
         // capture the peer settings and forward it
         if let Some(settings) = self.conn_mut()?.peer_settings_raw() {
+            let max_field_section_size = settings
+                .iter()
+                .find(|(id, _)| *id == SETTINGS_MAX_FIELD_SECTION_SIZE)
+                .map(|(_, value)| *value);
             let incoming_settings = H3Event::IncomingSettings {
                 settings: settings.to_vec(),
             };
 
+            self.pending_conn_metrics.settings_received = true;
+            // The peer's SETTINGS can only arrive on its control stream
+            self.pending_conn_metrics
+                .uni_streams_opened
+                .push(labels::H3StreamType::Control);
+            self.pending_conn_metrics.peer_max_field_section_size =
+                max_field_section_size;
+
             self.h3_event_sender
                 .send(incoming_settings.into())
                 .map_err(|_| H3ConnectionError::ControllerWentAway)?;
@@ -766,7 +924,9 @@ impl<H: DriverHooks> H3Driver<H> {
             match frame {
                 Ok(OutboundFrame::Datagram(dgram, flow_id)) => {
                     // Drop datagrams if there is no capacity
-                    let _ = datagram::send_h3_dgram(qconn, flow_id, dgram);
+                    if datagram::send_h3_dgram(qconn, flow_id, dgram).is_err() {
+                        self.pending_conn_metrics.dgram_drops += 1;
+                    }
                 },
                 Ok(OutboundFrame::FlowShutdown { flow_id, stream_id }) => {
                     self.finish_stream(
@@ -854,6 +1014,18 @@ impl<H: DriverHooks> H3Driver<H> {
             self.flow_map.remove(&mapped_flow_id);
         }
 
+        if let (Some(completed_at), true) =
+            (stream_ctx.request_completed_at, stream_ctx.fin_sent)
+        {
+            self.unacked_responses.push((stream_id, completed_at));
+        }
+
+        self.pending_stream_metrics
+            .push(StreamMetricsEvent::Closed {
+                audit_stats: stream_ctx.audit_stats,
+                is_server: qconn.is_server(),
+            });
+
         if qconn.is_server() {
             // Signal the server to remove the stream from its map
             let _ = self
@@ -864,6 +1036,29 @@ impl<H: DriverHooks> H3Driver<H> {
         Ok(())
     }
 
+    /// Reports the total duration of requests whose response has been
+    /// acknowledged.
+    ///
+    /// quiche doesn't report acknowledgements of stream data, but it only
+    /// forgets a stream once it is complete in both directions. The request
+    /// was already read in full, so a forgotten stream means the peer
+    /// acknowledged the whole response.
+    fn process_acked_responses(&mut self, qconn: &QuicheConnection) {
+        let events = &mut self.pending_stream_metrics;
+        self.unacked_responses.retain(|&(stream_id, completed_at)| {
+            let acked = matches!(
+                qconn.stream_capacity(stream_id),
+                Err(quiche::Error::InvalidStreamState(_))
+            );
+            if acked {
+                events.push(StreamMetricsEvent::RequestCompleted {
+                    duration: completed_at.elapsed(),
+                });
+            }
+            !acked
+        });
+    }
+
     /// Handles a regular [`H3Command`]. May be called internally by
     /// [DriverHooks] for non-endpoint-specific [`H3Command`]s.
     fn handle_core_command(
@@ -876,6 +1071,7 @@ impl<H: DriverHooks> H3Driver<H> {
                 self.conn_mut()
                     .expect("connection should be established")
                     .send_goaway(qconn, max_id)?;
+                self.pending_conn_metrics.goaways_sent += 1;
             },
         }
         Ok(())
@@ -911,11 +1107,46 @@ impl<H: DriverHooks> H3Driver<H> {
         };
 
         loop {
+            let headers_probe = match &ctx.queued_frame {
+                Some(OutboundFrame::Headers(headers)) =>
+                    Some(OutboundHeadersProbe::new(headers)),
+                _ => None,
+            };
+
             // Process each writable frame, queue the next frame for processing
             // and shut down any errored streams.
             match Self::process_write_frame(conn, qconn, ctx) {
-                Ok(()) => ctx.queued_frame = None,
-                Err(h3::Error::StreamBlocked | h3::Error::Done) => break,
+                Ok(()) => {
+                    ctx.queued_frame = None;
+                    if let Some(probe) = headers_probe {
+                        probe.on_sent(&mut self.pending_stream_metrics);
+                    }
+
+                    if let Some(received_at) = ctx.request_received_at.take() {
+                        self.pending_stream_metrics.push(
+                            StreamMetricsEvent::FirstByteSent {
+                                time_to_first_byte: received_at.elapsed(),
+                            },
+                        );
+                    }
+
+                    if let Some((since, direction)) = ctx.blocked_since.take() {
+                        self.pending_stream_metrics.push(
+                            StreamMetricsEvent::Unblocked {
+                                blocked_for: since.elapsed(),
+                                direction,
+                            },
+                        );
+                    }
+                },
+                Err(h3::Error::StreamBlocked | h3::Error::Done) => {
+                    if ctx.queued_frame.is_some() {
+                        ctx.blocked_since.get_or_insert_with(|| {
+                            (Instant::now(), blocked_direction(qconn))
+                        });
+                    }
+                    break;
+                },
                 Err(h3::Error::MessageError) => {
                     return self.finish_stream(
                         qconn,
@@ -968,14 +1199,22 @@ impl<H: DriverHooks> H3Driver<H> {
         // split metrics between local/peer and QUIC/HTTP/3 level errors
         if let Some(err) = qconn.local_error() {
             if err.is_app {
-                metrics.local_h3_conn_close_error_count(err.error_code.into())
+                let h3_err = labels::H3Error::from(err.error_code);
+                if let Some(qpack_err) = h3_err.qpack_error() {
+                    metrics.local_qpack_conn_close_error_count(qpack_err).inc();
+                }
+                metrics.local_h3_conn_close_error_count(h3_err)
             } else {
                 metrics.local_quic_conn_close_error_count(err.error_code.into())
             }
             .inc();
         } else if let Some(err) = qconn.peer_error() {
             if err.is_app {
-                metrics.peer_h3_conn_close_error_count(err.error_code.into())
+                let h3_err = labels::H3Error::from(err.error_code);
+                if let Some(qpack_err) = h3_err.qpack_error() {
+                    metrics.peer_qpack_conn_close_error_count(qpack_err).inc();
+                }
+                metrics.peer_h3_conn_close_error_count(h3_err)
             } else {
                 metrics.peer_quic_conn_close_error_count(err.error_code.into())
             }
@@ -991,6 +1230,14 @@ impl<H: DriverHooks> ApplicationOverQuic for H3Driver<H> {
     ) -> QuicResult<()> {
         let conn = h3::Connection::with_transport(quiche_conn, &self.h3_config)?;
         self.conn = Some(conn);
+        self.established_at = Some(Instant::now());
+        self.pending_conn_metrics.settings_sent = true;
+
+        // Creating the HTTP/3 connection fails if our control stream can't be
+        // opened
+        self.pending_conn_metrics
+            .uni_streams_opened
+            .push(labels::H3StreamType::Control);
 
         H::conn_established(self, quiche_conn, handshake_info)?;
         Ok(())
@@ -1027,6 +1274,7 @@ impl<H: DriverHooks> ApplicationOverQuic for H3Driver<H> {
             };
         }
 
+        self.process_acked_responses(qconn);
         self.process_available_dgrams(qconn)?;
         Ok(())
     }
@@ -1035,29 +1283,89 @@ impl<H: DriverHooks> ApplicationOverQuic for H3Driver<H> {
     /// all sources. This will attempt to write any queued frames into their
     /// respective streams, if writable.
     fn process_writes(&mut self, qconn: &mut QuicheConnection) -> QuicResult<()> {
+        let mut streams_written = 0;
+
         while let Some(stream_id) = qconn.stream_writable_next() {
             self.process_writable_stream(qconn, stream_id)?;
+            streams_written += 1;
         }
 
         // Also optimistically check for any ready streams
         while let Some(Some(ready)) = self.waiting_streams.next().now_or_never() {
             self.upstream_ready(qconn, ready)?;
+            streams_written += 1;
+        }
+
+        if streams_written > 0 {
+            self.pending_conn_metrics
+                .write_batch_sizes
+                .push(streams_written);
         }
 
         Ok(())
     }
 
+    /// Reports stream lifecycle and connection-level events to `metrics`.
+    fn report_metrics<M: Metrics>(&mut self, metrics: &M) {
+        for event in self.pending_stream_metrics.drain(..) {
+            event.report(metrics);
+        }
+
+        std::mem::take(&mut self.pending_conn_metrics).report(metrics);
+
+        // Counting the queued bytes walks all streams, so only sample them
+        // periodically rather than on every iteration of the worker loop.
+        let now = Instant::now();
+        if self.pending_send_bytes_sampled_at.is_some_and(|last| {
+            now.duration_since(last) < PENDING_SEND_BYTES_SAMPLE_INTERVAL
+        }) {
+            return;
+        }
+        self.pending_send_bytes_sampled_at = Some(now);
+
+        let pending_send_bytes = self.queued_body_bytes();
+        if pending_send_bytes != self.reported_pending_send_bytes {
+            let gauge = metrics.pending_send_data_bytes();
+            gauge.dec_by(self.reported_pending_send_bytes);
+            gauge.inc_by(pending_send_bytes);
+            self.reported_pending_send_bytes = pending_send_bytes;
+        }
+    }
+
+    fn streams_open(&self) -> Option<u64> {
+        Some(self.stream_map.len() as u64)
+    }
+
     /// Reports connection-level error metrics and forwards
     /// IOWorker errors to the associated [H3Controller].
     fn on_conn_close<M: Metrics>(
         &mut self, quiche_conn: &mut QuicheConnection, metrics: &M,
         work_loop_result: &QuicResult<()>,
     ) {
+        // Streams which are still open are closed along with the connection
+        self.report_metrics(metrics);
+        metrics
+            .pending_send_data_bytes()
+            .dec_by(std::mem::take(&mut self.reported_pending_send_bytes));
+        for stream in self.stream_map.values() {
+            StreamMetricsEvent::Closed {
+                audit_stats: Arc::clone(&stream.audit_stats),
+                is_server: quiche_conn.is_server(),
+            }
+            .report(metrics);
+        }
+
         let max_stream_seen = self.max_stream_seen;
         metrics
             .maximum_writable_streams()
             .observe(max_stream_seen as f64);
 
+        if self.streams_opened > 0 {
+            metrics
+                .streams_per_connection_histogram()
+                .observe(self.streams_opened as f64);
+        }
+
         let Err(work_loop_error) = work_loop_result else {
             return;
         };
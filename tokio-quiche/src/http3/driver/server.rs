@@ -25,6 +25,7 @@
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::sync::Arc;
+use std::time::Instant;
 
 use tokio::sync::mpsc;
 
@@ -39,6 +40,7 @@ use super::H3Event;
 use super::InboundHeaders;
 use super::IncomingH3Headers;
 use super::StreamCtx;
+use super::StreamMetricsEvent;
 use super::STREAM_CAPACITY;
 use crate::http3::settings::Http3Settings;
 use crate::http3::settings::Http3SettingsEnforcer;
@@ -129,6 +131,7 @@ impl ServerHooks {
             let _ = driver.get_or_insert_flow(flow_id)?;
             stream_ctx.associated_dgram_flow_id = Some(flow_id);
         }
+        stream_ctx.request_received_at = Some(Instant::now());
 
         let headers = IncomingH3Headers {
             stream_id,
@@ -143,6 +146,9 @@ impl ServerHooks {
             .waiting_streams
             .push(stream_ctx.wait_for_recv(stream_id));
         driver.insert_stream(stream_id, stream_ctx);
+        driver
+            .pending_stream_metrics
+            .push(StreamMetricsEvent::RequestReceived);
 
         driver
             .h3_event_sender
@@ -26,10 +26,16 @@
 
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
 
+use quiche::h3;
+use quiche::h3::NameValue;
 use tokio::sync::mpsc;
 use tokio_util::sync::PollSender;
 
@@ -40,6 +46,12 @@ use super::OutboundFrame;
 use super::OutboundFrameSender;
 use super::OutboundFrameStream;
 use crate::http3::H3AuditStats;
+use crate::metrics::labels::FlowControlDirection;
+use crate::metrics::labels::HttpStatusClass;
+use crate::metrics::labels::StreamDirection;
+use crate::metrics::labels::StreamInitiator;
+use crate::metrics::Metrics;
+use crate::quic::QuicheConnection;
 
 pub(crate) struct StreamCtx {
     /// Sends [`InboundFrame`]s to a local task, for example an `H3Body`.
@@ -59,6 +71,19 @@ pub(crate) struct StreamCtx {
     /// The flow ID for proxying datagrams over this stream. If `None`,
     /// the stream has no associated DATAGRAM flow.
     pub(crate) associated_dgram_flow_id: Option<u64>,
+    /// Time at which the stream was first blocked on send capacity, and the
+    /// limit which blocked it, if it is currently blocked.
+    pub(crate) blocked_since: Option<(Instant, FlowControlDirection)>,
+    /// Time at which all received body data was forwarded and the stream
+    /// started waiting for more data from the peer, if it is currently
+    /// waiting.
+    pub(crate) read_blocked_since: Option<Instant>,
+    /// Time at which the request on this stream was received. Cleared once the
+    /// first frame of the response is sent. Only set on servers.
+    pub(crate) request_received_at: Option<Instant>,
+    /// Time at which the last byte of the request on this stream was
+    /// received. Only set on servers.
+    pub(crate) request_completed_at: Option<Instant>,
 }
 
 impl StreamCtx {
@@ -82,6 +107,10 @@ impl StreamCtx {
             fin_sent: false,
 
             associated_dgram_flow_id: None,
+            blocked_since: None,
+            read_blocked_since: None,
+            request_received_at: None,
+            request_completed_at: None,
         };
 
         (ctx, PollSender::new(backward_sender), forward_receiver)
@@ -128,6 +157,213 @@ impl FlowCtx {
     }
 }
 
+/// A stream lifecycle event which has not been reported to [`Metrics`] yet.
+pub(crate) enum StreamMetricsEvent {
+    Opened {
+        stream_id: u64,
+    },
+    Closed {
+        audit_stats: Arc<H3AuditStats>,
+        is_server: bool,
+    },
+    Unblocked {
+        blocked_for: Duration,
+        direction: FlowControlDirection,
+    },
+    ReadUnblocked {
+        blocked_for: Duration,
+    },
+    FirstByteSent {
+        time_to_first_byte: Duration,
+    },
+    RequestCompleted {
+        duration: Duration,
+    },
+    RequestReceived,
+    ResponseSent(HttpStatusClass),
+    HeadersCompressed {
+        ratio: f64,
+    },
+}
+
+/// Returns the limit which most likely blocked a stream that could not be
+/// written to.
+///
+/// quiche doesn't expose its connection-level send capacity, but it reports no
+/// writable streams at all once that capacity is exhausted. A stream which hit
+/// its own limit is merely left out of the writable set.
+pub(crate) fn blocked_direction(
+    qconn: &QuicheConnection,
+) -> FlowControlDirection {
+    if qconn.writable().next().is_none() {
+        FlowControlDirection::Connection
+    } else {
+        FlowControlDirection::Stream
+    }
+}
+
+/// Only one in this many HEADERS frames is re-encoded to measure its
+/// compression ratio.
+const HEADER_COMPRESSION_SAMPLE_INTERVAL: u64 = 16;
+
+/// Number of [`OutboundHeadersProbe`]s created by all connections.
+static HEADERS_PROBED: AtomicU64 = AtomicU64::new(0);
+
+/// Metrics about a HEADERS frame which is about to be sent, to be reported
+/// once quiche accepted the frame.
+pub(crate) struct OutboundHeadersProbe {
+    status_class: Option<HttpStatusClass>,
+    compression_ratio: Option<f64>,
+}
+
+impl OutboundHeadersProbe {
+    pub(crate) fn new(headers: &[h3::Header]) -> Self {
+        let status_class = headers
+            .iter()
+            .find(|h| h.name() == b":status")
+            .and_then(|h| std::str::from_utf8(h.value()).ok()?.parse().ok())
+            .and_then(HttpStatusClass::from_status);
+
+        let uncompressed_len: usize = headers
+            .iter()
+            .map(|h| h.name().len() + h.value().len())
+            .sum();
+
+        // quiche's QPACK encoder only uses the static table, so encoding the
+        // headers again yields the same header block that quiche sends.
+        // Measuring the compression ratio encodes the headers a second time,
+        // so it's only done for a sample of the HEADERS frames.
+        let measure_compression = HEADERS_PROBED.fetch_add(1, Ordering::Relaxed) %
+            HEADER_COMPRESSION_SAMPLE_INTERVAL ==
+            0;
+        let compression_ratio = (measure_compression && uncompressed_len > 0)
+            .then(|| encoded_header_block_len(headers))
+            .flatten()
+            .map(|len| len as f64 / uncompressed_len as f64);
+
+        Self {
+            status_class,
+            compression_ratio,
+        }
+    }
+
+    /// Pushes the metrics for the sent HEADERS frame to `events`.
+    pub(crate) fn on_sent(self, events: &mut Vec<StreamMetricsEvent>) {
+        events.extend(self.status_class.map(StreamMetricsEvent::ResponseSent));
+        events.extend(
+            self.compression_ratio
+                .map(|ratio| StreamMetricsEvent::HeadersCompressed { ratio }),
+        );
+    }
+}
+
+/// Returns the length of the QPACK header block encoding `headers`.
+fn encoded_header_block_len(headers: &[h3::Header]) -> Option<usize> {
+    let headers_len = headers
+        .iter()
+        .fold(0, |acc, h| acc + h.value().len() + h.name().len() + 32);
+
+    let mut header_block = vec![0; headers_len];
+    h3::qpack::Encoder::new()
+        .encode(headers, &mut header_block)
+        .ok()
+}
+
+impl StreamMetricsEvent {
+    /// Updates the per-stream counters, gauge and histograms in `metrics`.
+    pub(crate) fn report(self, metrics: &impl Metrics) {
+        match self {
+            Self::Opened { stream_id } => {
+                metrics
+                    .streams_opened(
+                        StreamInitiator::from_stream_id(stream_id),
+                        StreamDirection::from_stream_id(stream_id),
+                    )
+                    .inc();
+                metrics.open_streams_gauge().inc();
+            },
+            Self::Closed {
+                audit_stats,
+                is_server,
+            } => {
+                let stream_id = audit_stats.stream_id();
+                let direction = StreamDirection::from_stream_id(stream_id);
+                metrics
+                    .streams_closed(
+                        StreamInitiator::from_stream_id(stream_id),
+                        direction,
+                    )
+                    .inc();
+                metrics.open_streams_gauge().dec();
+
+                let bytes_sent = audit_stats.downstream_bytes_sent();
+                let bytes_recvd = audit_stats.downstream_bytes_recvd();
+                metrics.stream_bytes_sent().observe(bytes_sent as f64);
+                metrics.stream_bytes_received().observe(bytes_recvd as f64);
+
+                // Bidirectional streams carry a request and its response
+                if direction == StreamDirection::Bidirectional {
+                    let (request_bytes, response_bytes) = if is_server {
+                        (bytes_recvd, bytes_sent)
+                    } else {
+                        (bytes_sent, bytes_recvd)
+                    };
+                    metrics
+                        .h3_request_body_size_bytes()
+                        .observe(request_bytes as f64);
+                    metrics
+                        .h3_response_body_size_bytes()
+                        .observe(response_bytes as f64);
+                }
+
+                // Error codes are -1 if no RESET_STREAM was received/sent
+                if let Ok(code) =
+                    u64::try_from(audit_stats.recvd_reset_stream_error_code())
+                {
+                    metrics.stream_reset_count(code.into()).inc();
+                }
+                if let Ok(code) =
+                    u64::try_from(audit_stats.sent_reset_stream_error_code())
+                {
+                    metrics.peer_stream_reset_count(code.into()).inc();
+                }
+            },
+            Self::Unblocked {
+                blocked_for,
+                direction,
+            } => {
+                metrics
+                    .stream_flow_control_blocked_duration(direction)
+                    .observe(blocked_for.as_nanos() as u64);
+            },
+            Self::ReadUnblocked { blocked_for } => {
+                metrics
+                    .read_blocked_duration()
+                    .observe(blocked_for.as_nanos() as u64);
+            },
+            Self::FirstByteSent { time_to_first_byte } => {
+                metrics
+                    .h3_time_to_first_byte()
+                    .observe(time_to_first_byte.as_nanos() as u64);
+            },
+            Self::RequestCompleted { duration } => {
+                metrics
+                    .h3_total_request_duration()
+                    .observe(duration.as_nanos() as u64);
+            },
+            Self::RequestReceived => {
+                metrics.h3_request_count().inc();
+            },
+            Self::ResponseSent(status_class) => {
+                metrics.h3_response_status_count(status_class).inc();
+            },
+            Self::HeadersCompressed { ratio } => {
+                metrics.h3_header_compression_ratio().observe(ratio);
+            },
+        }
+    }
+}
+
 pub(crate) enum WaitForStream {
     Downstream(WaitForDownstreamData),
     Upstream(WaitForUpstreamCapacity),
@@ -91,6 +91,8 @@
 //!   durations, including protocol overhead and network delays.
 //! - `tokio-task-metrics`: Scheduling & poll duration histograms for tokio
 //!   tasks.
+//! - `tokio-task-cpu-metrics`: CPU time spent by worker threads polling tokio
+//!   tasks (implies `tokio-task-metrics`).
 
 #[cfg(not(feature = "gcongestion"))]
 pub extern crate quiche;
@@ -124,6 +126,7 @@ pub use crate::http3::driver::ServerH3Controller;
 pub use crate::http3::driver::ServerH3Driver;
 pub use crate::http3::ClientH3Connection;
 pub use crate::http3::ServerH3Connection;
+pub use crate::metrics::NullMetrics;
 pub use crate::quic::connection::ApplicationOverQuic;
 pub use crate::quic::connection::ConnectionIdGenerator;
 pub use crate::quic::connection::InitialQuicConnection;
@@ -0,0 +1,317 @@
+// Copyright (C) 2025, Cloudflare, Inc.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+// IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+// THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+// PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Connection-lifetime summaries of arbitrary measurements.
+
+use foundations::telemetry::metrics::Histogram;
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use super::Metrics;
+
+/// Summary statistic computed over all samples of a measurement.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Summary {
+    Min,
+    Max,
+    Mean,
+    /// Median of the retained samples, using the nearest-rank method
+    P50,
+    /// 99th percentile of the retained samples, using the nearest-rank method
+    P99,
+}
+
+impl Summary {
+    const ALL: [Summary; 5] = [
+        Summary::Min,
+        Summary::Max,
+        Summary::Mean,
+        Summary::P50,
+        Summary::P99,
+    ];
+
+    /// Computes the statistic for `measurement`, whose retained samples must
+    /// be non-empty and sorted.
+    fn compute<M>(self, measurement: &Measurement<M>, sorted: &[f64]) -> f64 {
+        match self {
+            Summary::Min => measurement.min,
+            Summary::Max => measurement.max,
+            Summary::Mean => measurement.sum / measurement.count as f64,
+            Summary::P50 => percentile(sorted, 0.5),
+            Summary::P99 => percentile(sorted, 0.99),
+        }
+    }
+}
+
+/// Nearest-rank percentile `p` of non-empty, sorted `samples`.
+fn percentile(samples: &[f64], p: f64) -> f64 {
+    let rank = (p * samples.len() as f64).ceil() as usize;
+    samples[rank.saturating_sub(1)]
+}
+
+/// Maximum number of samples retained per measurement for percentiles.
+const MAX_RETAINED_SAMPLES: usize = 1024;
+
+/// Source of unique [`ConnectionStatsAggregator`] IDs.
+static NEXT_AGGREGATOR_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Selects the histogram a [`Summary`] of a measurement is recorded in, or
+/// [`None`] to skip that statistic.
+pub type SummaryHistogram<M> = fn(&M, Summary) -> Option<Histogram>;
+
+/// Handle to a measurement registered with a [`ConnectionStatsAggregator`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MeasurementId {
+    aggregator: usize,
+    index: usize,
+}
+
+struct Measurement<M> {
+    histogram: SummaryHistogram<M>,
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+    /// The most recent samples, up to [`MAX_RETAINED_SAMPLES`].
+    samples: VecDeque<f64>,
+}
+
+impl<M> Measurement<M> {
+    fn new(histogram: SummaryHistogram<M>) -> Self {
+        Self {
+            histogram,
+            count: 0,
+            sum: 0.,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            samples: VecDeque::new(),
+        }
+    }
+
+    fn record(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+
+        if self.samples.len() == MAX_RETAINED_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    /// Computes every [`Summary`], or nothing if no sample was recorded.
+    ///
+    /// This sorts the retained samples in place, so it must only be called
+    /// once no further samples will be recorded.
+    fn summarize(&mut self) -> Vec<(Summary, f64)> {
+        if self.count == 0 {
+            return Vec::new();
+        }
+
+        let mut sorted = std::mem::take(&mut self.samples);
+        sorted.make_contiguous().sort_unstable_by(f64::total_cmp);
+        let summaries = Summary::ALL
+            .into_iter()
+            .map(|s| (s, s.compute(self, sorted.as_slices().0)))
+            .collect();
+
+        self.samples = sorted;
+        summaries
+    }
+}
+
+/// Accumulates measurements over the lifetime of a connection and records
+/// their [`Summary`] statistics in histograms when dropped.
+///
+/// [`Summary::Min`], [`Summary::Max`] and [`Summary::Mean`] cover every
+/// sample. Percentiles are computed over the most recent 1024 samples of each
+/// measurement, which bounds memory on long-lived connections. The aggregator
+/// can be shared across tasks, e.g. in an [`Arc`](std::sync::Arc).
+///
+/// # Example
+/// ```
+/// use tokio_quiche::metrics::aggregator::ConnectionStatsAggregator;
+/// use tokio_quiche::metrics::aggregator::Summary;
+/// use tokio_quiche::metrics::DefaultMetrics;
+/// use tokio_quiche::metrics::Metrics;
+///
+/// let aggregator = ConnectionStatsAggregator::new(DefaultMetrics);
+/// let bandwidth = aggregator.register(|m, summary| {
+///     (summary == Summary::Max).then(|| m.max_bandwidth_mbps())
+/// });
+///
+/// aggregator.record(bandwidth, 12.5);
+/// aggregator.record(bandwidth, 40.);
+/// // Records 40 in `max_bandwidth_mbps`
+/// drop(aggregator);
+/// ```
+pub struct ConnectionStatsAggregator<M: Metrics> {
+    id: usize,
+    metrics: M,
+    measurements: Mutex<Vec<Measurement<M>>>,
+}
+
+impl<M: Metrics> ConnectionStatsAggregator<M> {
+    /// Creates an aggregator recording into `metrics`.
+    pub fn new(metrics: M) -> Self {
+        Self {
+            id: NEXT_AGGREGATOR_ID.fetch_add(1, Ordering::Relaxed),
+            metrics,
+            measurements: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The [`Metrics`] this aggregator records into.
+    pub fn metrics(&self) -> &M {
+        &self.metrics
+    }
+
+    /// Registers a new measurement whose summaries are recorded in the
+    /// histograms selected by `histogram`.
+    pub fn register(&self, histogram: SummaryHistogram<M>) -> MeasurementId {
+        let mut measurements = self.lock();
+        measurements.push(Measurement::new(histogram));
+
+        MeasurementId {
+            aggregator: self.id,
+            index: measurements.len() - 1,
+        }
+    }
+
+    /// Adds a sample to the measurement identified by `id`.
+    ///
+    /// # Panics
+    /// Panics if `id` was registered with a different aggregator.
+    pub fn record(&self, id: MeasurementId, value: f64) {
+        assert_eq!(
+            id.aggregator, self.id,
+            "measurement registered with a different aggregator"
+        );
+        self.lock()[id.index].record(value);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Measurement<M>>> {
+        self.measurements
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<M: Metrics> Drop for ConnectionStatsAggregator<M> {
+    fn drop(&mut self) {
+        let measurements = self
+            .measurements
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        for measurement in measurements.iter_mut() {
+            for (summary, value) in measurement.summarize() {
+                if let Some(h) = (measurement.histogram)(&self.metrics, summary) {
+                    h.observe(value);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::DefaultMetrics;
+
+    fn measurement(values: impl IntoIterator<Item = f64>) -> Measurement<()> {
+        let mut m = Measurement::new(|_, _| None);
+        for v in values {
+            m.record(v);
+        }
+        m
+    }
+
+    fn summary(m: &mut Measurement<()>, summary: Summary) -> Option<f64> {
+        m.summarize()
+            .into_iter()
+            .find_map(|(s, v)| (s == summary).then_some(v))
+    }
+
+    #[test]
+    fn summaries() {
+        let mut m = measurement((1..=100).rev().map(f64::from));
+
+        assert_eq!(summary(&mut m, Summary::Min), Some(1.));
+        assert_eq!(summary(&mut m, Summary::Max), Some(100.));
+        assert_eq!(summary(&mut m, Summary::Mean), Some(50.5));
+        assert_eq!(summary(&mut m, Summary::P50), Some(50.));
+        assert_eq!(summary(&mut m, Summary::P99), Some(99.));
+    }
+
+    #[test]
+    fn percentiles_of_few_samples() {
+        assert_eq!(percentile(&[7.], 0.5), 7.);
+        assert_eq!(percentile(&[7.], 0.99), 7.);
+        assert_eq!(percentile(&[1., 2.], 0.5), 1.);
+        assert_eq!(percentile(&[1., 2.], 0.99), 2.);
+    }
+
+    #[test]
+    fn no_samples() {
+        let mut m = measurement([]);
+        assert!(m.summarize().is_empty());
+    }
+
+    #[test]
+    fn retained_samples_are_bounded() {
+        let total = MAX_RETAINED_SAMPLES * 2;
+        let mut m = measurement((0..total).map(|v| v as f64));
+
+        assert_eq!(m.samples.len(), MAX_RETAINED_SAMPLES);
+        assert_eq!(m.count, total as u64);
+        // Min and mean cover all samples, percentiles only the retained ones.
+        assert_eq!(summary(&mut m, Summary::Min), Some(0.));
+        assert_eq!(
+            summary(&mut m, Summary::Mean),
+            Some((total - 1) as f64 / 2.)
+        );
+        assert_eq!(
+            summary(&mut m, Summary::P50),
+            Some((MAX_RETAINED_SAMPLES + MAX_RETAINED_SAMPLES / 2 - 1) as f64)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "different aggregator")]
+    fn foreign_measurement_id_panics() {
+        let a = ConnectionStatsAggregator::new(DefaultMetrics);
+        let b = ConnectionStatsAggregator::new(DefaultMetrics);
+
+        let _ = b.register(|_, _| None);
+        let id = a.register(|_, _| None);
+        b.record(id, 1.);
+    }
+}
@@ -0,0 +1,438 @@
+// Copyright (C) 2025, Cloudflare, Inc.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+// IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+// THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+// PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::sync::OnceLock;
+
+static BUCKET_CONFIG: OnceLock<MetricsBucketConfig> = OnceLock::new();
+
+/// Bucket boundaries of the histograms reported by [`DefaultMetrics`].
+///
+/// Each field holds the upper bounds of the buckets of the [`Metrics`]
+/// histogram with the same name. The [`Default`] implementation matches the
+/// boundaries used when no configuration is provided. New fields may be added
+/// over time, so start from the default configuration and override the fields
+/// of interest.
+///
+/// [`DefaultMetrics`]: super::DefaultMetrics
+/// [`Metrics`]: super::Metrics
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct MetricsBucketConfig {
+    /// Buckets of the `connection_duration_seconds` histogram
+    pub connection_duration_seconds: &'static [f64],
+    /// Buckets of the `maximum_writable_streams` histogram
+    pub maximum_writable_streams: &'static [f64],
+    /// Buckets of the `handshake_time_seconds` histogram
+    pub handshake_time_seconds: &'static [f64],
+    /// Buckets of the `token_validation_duration` histogram
+    pub token_validation_duration: &'static [f64],
+    /// Buckets of the `packet_dispatch_latency` histogram
+    pub packet_dispatch_latency: &'static [f64],
+    /// Buckets of the `udp_recv_syscall_duration` histogram
+    pub udp_recv_syscall_duration: &'static [f64],
+    /// Buckets of the `udp_send_syscall_duration` histogram
+    pub udp_send_syscall_duration: &'static [f64],
+    /// Buckets of the `max_bandwidth_mbps` histogram
+    pub max_bandwidth_mbps: &'static [f64],
+    /// Buckets of the `max_loss_pct` histogram
+    pub max_loss_pct: &'static [f64],
+    /// Buckets of the `rtt_micros` histogram
+    pub rtt_micros: &'static [f64],
+    /// Buckets of the `min_rtt_micros` histogram
+    pub min_rtt_micros: &'static [f64],
+    /// Buckets of the `max_rtt_micros` histogram
+    pub max_rtt_micros: &'static [f64],
+    /// Buckets of the `sent_packet_size_bytes` histogram
+    pub sent_packet_size_bytes: &'static [f64],
+    /// Buckets of the `received_packet_size_bytes` histogram
+    pub received_packet_size_bytes: &'static [f64],
+    /// Buckets of the `gso_batch_size` histogram
+    pub gso_batch_size: &'static [f64],
+    /// Buckets of the `packet_coalescing_ratio` histogram
+    pub packet_coalescing_ratio: &'static [f64],
+    /// Buckets of the `write_batch_size` histogram
+    pub write_batch_size: &'static [f64],
+    /// Buckets of the `certificate_chain_depth` histogram
+    pub certificate_chain_depth: &'static [f64],
+    /// Buckets of the `early_data_size_bytes` histogram
+    pub early_data_size_bytes: &'static [f64],
+    /// Buckets of the `handshake_packet_count` histogram
+    pub handshake_packet_count: &'static [f64],
+    /// Buckets of the `active_connection_id_count` histogram
+    pub active_connection_id_count: &'static [f64],
+    /// Buckets of the `packet_loss_rate_pct` histogram
+    pub packet_loss_rate_pct: &'static [f64],
+    /// Buckets of the `write_blocked_duration` histogram
+    pub write_blocked_duration: &'static [f64],
+    /// Buckets of the `read_blocked_duration` histogram
+    pub read_blocked_duration: &'static [f64],
+    /// Buckets of the `bandwidth_estimate_mbps` histogram
+    pub bandwidth_estimate_mbps: &'static [f64],
+    /// Buckets of the `connection_age_at_first_stream` histogram
+    pub connection_age_at_first_stream: &'static [f64],
+    /// Buckets of the `streams_per_connection_histogram` histogram
+    pub streams_per_connection_histogram: &'static [f64],
+    /// Buckets of the `h3_request_body_size_bytes` histogram
+    pub h3_request_body_size_bytes: &'static [f64],
+    /// Buckets of the `h3_response_body_size_bytes` histogram
+    pub h3_response_body_size_bytes: &'static [f64],
+    /// Buckets of the `h3_time_to_first_byte` histogram
+    pub h3_time_to_first_byte: &'static [f64],
+    /// Buckets of the `h3_total_request_duration` histogram
+    pub h3_total_request_duration: &'static [f64],
+    /// Buckets of the `max_ack_delay_micros` histogram
+    pub max_ack_delay_micros: &'static [f64],
+    /// Buckets of the `stream_bytes_sent` histogram
+    pub stream_bytes_sent: &'static [f64],
+    /// Buckets of the `stream_bytes_received` histogram
+    pub stream_bytes_received: &'static [f64],
+    /// Buckets of the `stream_flow_control_blocked_duration` histogram
+    pub stream_flow_control_blocked_duration: &'static [f64],
+    /// Buckets of the `path_validation_duration` histogram
+    pub path_validation_duration: &'static [f64],
+    /// Buckets of the `h3_header_compression_ratio` histogram
+    pub h3_header_compression_ratio: &'static [f64],
+    /// Buckets of the `h3_settings_max_field_section_size_histogram` histogram
+    pub h3_settings_max_field_section_size_histogram: &'static [f64],
+    /// Buckets of the `tokio_runtime_task_schedule_delay_histogram` histogram
+    pub tokio_runtime_task_schedule_delay_histogram: &'static [f64],
+    /// Buckets of the `tokio_runtime_task_poll_duration_histogram` histogram
+    pub tokio_runtime_task_poll_duration_histogram: &'static [f64],
+}
+
+impl Default for MetricsBucketConfig {
+    fn default() -> Self {
+        Self {
+            connection_duration_seconds: &[
+                1E-3, 2E-3, 5E-3, 1E-2, 2E-2, 5E-2, 0.1, 0.2, 0.5, 1.0, 2.0, 5.0,
+                10.0, 30.0, 60.0, 300.0, 600.0, 1800.0, 3600.0, 7200.0, 14400.0,
+                28800.0, 86400.0,
+            ],
+            maximum_writable_streams: &[
+                0.0, 5.0, 10.0, 100.0, 1000.0, 2000.0, 3000.0, 10000.0, 20000.0,
+                50000.0,
+            ],
+            handshake_time_seconds: &[
+                1E-5, 2E-5, 5E-5, 1E-4, 2E-4, 5E-4, 1E-3, 2E-3, 5E-3, 1E-2, 2E-2,
+                5E-2, 0.1, 0.2, 0.5, 1.0, 2.0, 5.0,
+            ],
+            token_validation_duration: &[
+                1E-6, 5E-6, 1E-5, 5E-5, 1E-4, 5E-4, 1E-3,
+            ],
+            packet_dispatch_latency: &[
+                1E-7, 2.5E-7, 5E-7, 1E-6, 2.5E-6, 5E-6, 1E-5, 5E-5, 1E-4,
+            ],
+            udp_recv_syscall_duration: &[
+                1E-7, 2.5E-7, 5E-7, 1E-6, 2.5E-6, 5E-6, 1E-5, 5E-5, 1E-4, 1E-3,
+            ],
+            udp_send_syscall_duration: &[
+                1E-7, 2.5E-7, 5E-7, 1E-6, 2.5E-6, 5E-6, 1E-5, 5E-5, 1E-4, 1E-3,
+            ],
+            max_bandwidth_mbps: &[
+                0., 1., 2., 5., 10., 20., 50., 100., 200., 300., 500., 750.,
+                1000., 1500., 2000., 2500., 3000., 3500., 4000., 4500., 5000.,
+                6000., 7000., 10000.,
+            ],
+            max_loss_pct: &[
+                0.0, 0.001, 0.005, 0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1., 2., 3.,
+                4., 5., 10., 15., 20., 25., 50., 100.,
+            ],
+            rtt_micros: &[
+                100., 250., 500., 1000., 2000., 5000., 10000., 20000., 50000.,
+                100000., 500000.,
+            ],
+            min_rtt_micros: &[
+                100., 250., 500., 1000., 2000., 5000., 10000., 20000., 50000.,
+                100000., 500000.,
+            ],
+            max_rtt_micros: &[
+                100., 250., 500., 1000., 2000., 5000., 10000., 20000., 50000.,
+                100000., 500000.,
+            ],
+            sent_packet_size_bytes: &[
+                0., 100., 500., 1000., 1200., 1280., 1350., 1450., 1500.,
+            ],
+            received_packet_size_bytes: &[
+                0., 100., 500., 1000., 1200., 1280., 1350., 1450., 1500.,
+            ],
+            gso_batch_size: &[1., 2., 4., 8., 16., 32., 64.],
+            packet_coalescing_ratio: &[1.0, 1.5, 2.0, 2.5, 3.0, 4.0, 5.0],
+            write_batch_size: &[
+                1., 2., 4., 8., 16., 32., 64., 128., 256., 512., 1024.,
+            ],
+            certificate_chain_depth: &[1., 2., 3., 4., 5., 6.],
+            early_data_size_bytes: &[1E2, 5E2, 1E3, 2E3, 5E3, 1E4, 2E4, 5E4, 1E5],
+            handshake_packet_count: &[
+                1., 2., 3., 4., 5., 6., 8., 10., 15., 20., 30., 50.,
+            ],
+            active_connection_id_count: &[1., 2., 4., 8., 16.],
+            packet_loss_rate_pct: &[
+                0.0, 0.001, 0.005, 0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1., 2., 3.,
+                4., 5., 10., 15., 20., 25., 50., 100.,
+            ],
+            write_blocked_duration: &[
+                1E-5, 5E-5, 1E-4, 5E-4, 1E-3, 5E-3, 1E-2, 5E-2, 0.1, 0.5, 1.0,
+            ],
+            read_blocked_duration: &[
+                1E-3, 5E-3, 1E-2, 5E-2, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+            ],
+            bandwidth_estimate_mbps: &[
+                0., 1., 2., 5., 10., 20., 50., 100., 200., 300., 500., 750.,
+                1000., 1500., 2000., 2500., 3000., 3500., 4000., 4500., 5000.,
+                6000., 7000., 10000.,
+            ],
+            connection_age_at_first_stream: &[
+                1E-3, 5E-3, 1E-2, 5E-2, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+                30.0, 60.0,
+            ],
+            streams_per_connection_histogram: &[
+                1., 2., 5., 10., 20., 50., 100., 200., 500., 1000., 10000.,
+            ],
+            h3_request_body_size_bytes: &[
+                0., 1E1, 1E2, 1E3, 1E4, 1E5, 1E6, 1E7, 1E8, 1E9,
+            ],
+            h3_response_body_size_bytes: &[
+                0., 1E1, 1E2, 1E3, 1E4, 1E5, 1E6, 1E7, 1E8, 1E9,
+            ],
+            h3_time_to_first_byte: &[
+                1E-5, 2E-5, 5E-5, 1E-4, 2E-4, 5E-4, 1E-3, 2E-3, 5E-3, 1E-2, 2E-2,
+                5E-2, 0.1, 0.2, 0.5, 1.0, 2.0, 5.0,
+            ],
+            h3_total_request_duration: &[
+                1E-4, 5E-4, 1E-3, 5E-3, 1E-2, 5E-2, 0.1, 0.25, 0.5, 1.0, 2.5,
+                5.0, 10.0, 30.0, 60.0,
+            ],
+            max_ack_delay_micros: &[
+                1000., 5000., 10000., 25000., 50000., 100000., 250000., 500000.,
+                1000000., 16384000.,
+            ],
+            stream_bytes_sent: &[0., 1E2, 1E3, 1E4, 1E5, 1E6, 1E7, 1E8, 1E9],
+            stream_bytes_received: &[0., 1E2, 1E3, 1E4, 1E5, 1E6, 1E7, 1E8, 1E9],
+            stream_flow_control_blocked_duration: &[
+                1E-3, 5E-3, 1E-2, 5E-2, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+            ],
+            path_validation_duration: &[
+                0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1., 2.5,
+            ],
+            h3_header_compression_ratio: &[
+                0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0,
+            ],
+            h3_settings_max_field_section_size_histogram: &[
+                1024., 4096., 8192., 16384., 32768., 65536., 131072., 262144.,
+                1048576.,
+            ],
+            tokio_runtime_task_schedule_delay_histogram: &[
+                0.0, 1E-4, 2E-4, 3E-4, 4E-4, 5E-4, 6E-4, 7E-4, 8E-4, 9E-4, 1E-3,
+                1E-2, 2E-2, 4E-2, 8E-2, 1E-1, 1.0,
+            ],
+            tokio_runtime_task_poll_duration_histogram: &[
+                0.0, 1E-4, 2E-4, 3E-4, 4E-4, 5E-4, 6E-4, 7E-4, 8E-4, 9E-4, 1E-3,
+                1E-2, 2E-2, 4E-2, 8E-2, 1E-1, 1.0,
+            ],
+        }
+    }
+}
+
+/// Error returned when a [`MetricsBucketConfig`] can't be installed.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum BucketConfigError {
+    /// The bucket boundaries of the named histogram are not in strictly
+    /// ascending order.
+    #[error("buckets of the `{0}` histogram are not in ascending order")]
+    NotAscending(&'static str),
+    /// A configuration was already installed, or histograms were already
+    /// created with the default one.
+    #[error("a bucket configuration is already in use")]
+    AlreadyInstalled,
+}
+
+impl MetricsBucketConfig {
+    /// Installs `self` as the process-wide bucket configuration.
+    pub(super) fn install(self) -> Result<(), BucketConfigError> {
+        self.install_in(&BUCKET_CONFIG)
+    }
+
+    /// Validates `self` and stores it in `cell`, unless `cell` already holds
+    /// a configuration.
+    fn install_in(self, cell: &OnceLock<Self>) -> Result<(), BucketConfigError> {
+        self.validate()?;
+        cell.set(self)
+            .map_err(|_| BucketConfigError::AlreadyInstalled)
+    }
+
+    /// Checks that the buckets of every histogram are in ascending order.
+    fn validate(&self) -> Result<(), BucketConfigError> {
+        for (name, buckets) in self.histograms() {
+            if !buckets.windows(2).all(|w| w[0] < w[1]) {
+                return Err(BucketConfigError::NotAscending(name));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the name and buckets of every histogram.
+    fn histograms(&self) -> [(&'static str, &'static [f64]); 40] {
+        [
+            (
+                "connection_duration_seconds",
+                self.connection_duration_seconds,
+            ),
+            ("maximum_writable_streams", self.maximum_writable_streams),
+            ("handshake_time_seconds", self.handshake_time_seconds),
+            ("token_validation_duration", self.token_validation_duration),
+            ("packet_dispatch_latency", self.packet_dispatch_latency),
+            ("udp_recv_syscall_duration", self.udp_recv_syscall_duration),
+            ("udp_send_syscall_duration", self.udp_send_syscall_duration),
+            ("max_bandwidth_mbps", self.max_bandwidth_mbps),
+            ("max_loss_pct", self.max_loss_pct),
+            ("rtt_micros", self.rtt_micros),
+            ("min_rtt_micros", self.min_rtt_micros),
+            ("max_rtt_micros", self.max_rtt_micros),
+            ("sent_packet_size_bytes", self.sent_packet_size_bytes),
+            (
+                "received_packet_size_bytes",
+                self.received_packet_size_bytes,
+            ),
+            ("gso_batch_size", self.gso_batch_size),
+            ("packet_coalescing_ratio", self.packet_coalescing_ratio),
+            ("write_batch_size", self.write_batch_size),
+            ("certificate_chain_depth", self.certificate_chain_depth),
+            ("early_data_size_bytes", self.early_data_size_bytes),
+            ("handshake_packet_count", self.handshake_packet_count),
+            (
+                "active_connection_id_count",
+                self.active_connection_id_count,
+            ),
+            ("packet_loss_rate_pct", self.packet_loss_rate_pct),
+            ("write_blocked_duration", self.write_blocked_duration),
+            ("read_blocked_duration", self.read_blocked_duration),
+            ("bandwidth_estimate_mbps", self.bandwidth_estimate_mbps),
+            (
+                "connection_age_at_first_stream",
+                self.connection_age_at_first_stream,
+            ),
+            (
+                "streams_per_connection_histogram",
+                self.streams_per_connection_histogram,
+            ),
+            (
+                "h3_request_body_size_bytes",
+                self.h3_request_body_size_bytes,
+            ),
+            (
+                "h3_response_body_size_bytes",
+                self.h3_response_body_size_bytes,
+            ),
+            ("h3_time_to_first_byte", self.h3_time_to_first_byte),
+            ("h3_total_request_duration", self.h3_total_request_duration),
+            ("max_ack_delay_micros", self.max_ack_delay_micros),
+            ("stream_bytes_sent", self.stream_bytes_sent),
+            ("stream_bytes_received", self.stream_bytes_received),
+            (
+                "stream_flow_control_blocked_duration",
+                self.stream_flow_control_blocked_duration,
+            ),
+            ("path_validation_duration", self.path_validation_duration),
+            (
+                "h3_header_compression_ratio",
+                self.h3_header_compression_ratio,
+            ),
+            (
+                "h3_settings_max_field_section_size_histogram",
+                self.h3_settings_max_field_section_size_histogram,
+            ),
+            (
+                "tokio_runtime_task_schedule_delay_histogram",
+                self.tokio_runtime_task_schedule_delay_histogram,
+            ),
+            (
+                "tokio_runtime_task_poll_duration_histogram",
+                self.tokio_runtime_task_poll_duration_histogram,
+            ),
+        ]
+    }
+}
+
+/// Returns the process-wide bucket configuration, falling back to the default
+/// one if none was installed.
+pub(super) fn bucket_config() -> &'static MetricsBucketConfig {
+    BUCKET_CONFIG.get_or_init(MetricsBucketConfig::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_is_valid() {
+        assert_eq!(MetricsBucketConfig::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn install_in_sets_config_once() {
+        let cell = OnceLock::new();
+
+        let config = MetricsBucketConfig {
+            rtt_micros: &[1.0, 10.0, 100.0],
+            ..Default::default()
+        };
+        assert_eq!(config.install_in(&cell), Ok(()));
+        assert_eq!(cell.get().unwrap().rtt_micros, &[1.0, 10.0, 100.0]);
+
+        assert_eq!(
+            MetricsBucketConfig::default().install_in(&cell),
+            Err(BucketConfigError::AlreadyInstalled)
+        );
+        assert_eq!(cell.get().unwrap().rtt_micros, &[1.0, 10.0, 100.0]);
+    }
+
+    #[test]
+    fn install_in_rejects_unordered_buckets() {
+        let cell = OnceLock::new();
+
+        let config = MetricsBucketConfig {
+            gso_batch_size: &[1.0, 4.0, 2.0],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.install_in(&cell),
+            Err(BucketConfigError::NotAscending("gso_batch_size"))
+        );
+        assert!(cell.get().is_none());
+
+        let config = MetricsBucketConfig {
+            max_loss_pct: &[1.0, 1.0],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.install_in(&cell),
+            Err(BucketConfigError::NotAscending("max_loss_pct"))
+        );
+        assert!(cell.get().is_none());
+    }
+}
@@ -100,6 +100,57 @@ impl From<&BoxError> for HandshakeError {
     }
 }
 
+/// Reason why a QUIC handshake was aborted before the TLS handshake could
+/// fail on its own.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandshakeAbortReason {
+    BufferOverflow,
+    ParseError,
+    Timeout,
+    /// The peer closed the connection during the handshake.
+    PeerClosed,
+    Unknown,
+}
+
+impl HandshakeAbortReason {
+    /// Classifies a handshake failure. Returns `None` for TLS and crypto
+    /// failures, which are already reported by [`HandshakeError`].
+    pub fn from_error(err: &BoxError) -> Option<Self> {
+        if let Some(e) = err.downcast_ref::<quic::HandshakeError>() {
+            return Some(match e {
+                quic::HandshakeError::Timeout => Self::Timeout,
+                quic::HandshakeError::ConnectionClosed => Self::PeerClosed,
+            });
+        }
+
+        match err.downcast_ref::<quiche::Error>() {
+            Some(quiche::Error::CryptoFail | quiche::Error::TlsFail) => None,
+            Some(quiche::Error::BufferTooShort) => Some(Self::BufferOverflow),
+            Some(
+                quiche::Error::InvalidPacket |
+                quiche::Error::InvalidFrame |
+                quiche::Error::InvalidTransportParam,
+            ) => Some(Self::ParseError),
+            _ => Some(Self::Unknown),
+        }
+    }
+}
+
+/// Reason why a connection attempt failed before a handshake was started.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreHandshakeError {
+    /// The packet header could not be parsed.
+    ParseError,
+    /// The client offered a QUIC version we don't support.
+    VersionMismatch,
+    /// A non-Initial packet carried a connection ID we did not issue.
+    InvalidCid,
+    /// The server had no capacity left to accept the connection.
+    ResourceExhausted,
+}
+
 /// Reason why a QUIC Initial was discarded by the packet router.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum QuicInvalidInitialPacketError {
@@ -148,47 +199,34 @@ impl From<QuicInvalidInitialPacketError> for std::io::Error {
     }
 }
 
+/// Resource limit that caused the server to reject a new connection.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerOverloadReason {
+    /// The queue of accepted connections waiting for the application is full.
+    AcceptQueueFull,
+}
+
+/// Type of an HTTP/3 unidirectional stream.
+///
+/// quiche 0.23.7 reads the type of the peer's unidirectional streams itself
+/// and doesn't report it, so only control streams are tracked: the peer's is
+/// known to exist once its SETTINGS arrive. QPACK, push and unknown
+/// (including reserved GREASE) stream types can't be observed.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum H3StreamType {
+    Control,
+}
+
 /// HTTP/3 error code (from IANA registry).
 #[derive(Clone, Eq, Hash, PartialEq)]
 pub struct H3Error(u64);
 
 impl Serialize for H3Error {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let code = self.0;
-
-        // https://www.iana.org/assignments/http3-parameters/http3-parameters.xhtml
-        let v = if code > 0x21 && (code - 0x21) % 0x1f == 0 {
-            "H3_GREASE"
-        } else {
-            match code {
-                0x33 => "H3_DATAGRAM_ERROR",
-
-                0x100 => "H3_NO_ERROR",
-                0x101 => "H3_GENERAL_PROTOCOL_ERROR",
-                0x102 => "H3_INTERNAL_ERROR",
-                0x103 => "H3_STREAM_CREATION_ERROR",
-                0x104 => "H3_CLOSED_CRITICAL_STREAM",
-                0x105 => "H3_FRAME_UNEXPECTED",
-                0x106 => "H3_FRAME_ERROR",
-                0x107 => "H3_EXCESSIVE_LOAD",
-                0x108 => "H3_ID_ERROR",
-                0x109 => "H3_SETTINGS_ERROR",
-                0x10a => "H3_MISSING_SETTINGS",
-                0x10b => "H3_REQUEST_REJECTED",
-                0x10c => "H3_REQUEST_CANCELLED",
-                0x10d => "H3_REQUEST_INCOMPLETE",
-                0x10e => "H3_MESSAGE_ERROR",
-                0x10f => "H3_CONNECT_ERROR",
-                0x110 => "H3_VERSION_FALLBACK",
-
-                0x200 => "QPACK_DECOMPRESSION_FAILED",
-                0x201 => "QPACK_ENCODER_STREAM_ERROR",
-                0x202 => "QPACK_DECODER_STREAM_ERROR",
-
-                _ => "H3_UNKNOWN",
-            }
-        };
-        serializer.serialize_str(v)
+        serializer
+            .serialize_str(h3_error_code_name(self.0).unwrap_or("H3_UNKNOWN"))
     }
 }
 
@@ -198,6 +236,97 @@ impl From<u64> for H3Error {
     }
 }
 
+impl H3Error {
+    /// Returns the QPACK error this error code represents, if any.
+    pub fn qpack_error(&self) -> Option<QpackError> {
+        match self.0 {
+            0x200 => Some(QpackError::DecompressionFailed),
+            0x201 => Some(QpackError::EncoderStreamError),
+            0x202 => Some(QpackError::DecoderStreamError),
+            _ => None,
+        }
+    }
+}
+
+/// QPACK error code (from IANA registry).
+///
+/// These are a subset of the HTTP/3 error codes reported by [`H3Error`].
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+pub enum QpackError {
+    #[serde(rename = "QPACK_DECOMPRESSION_FAILED")]
+    DecompressionFailed,
+    #[serde(rename = "QPACK_ENCODER_STREAM_ERROR")]
+    EncoderStreamError,
+    #[serde(rename = "QPACK_DECODER_STREAM_ERROR")]
+    DecoderStreamError,
+}
+
+/// TLS alert code (from IANA registry).
+///
+/// Well-known alerts are displayed by name. Any other code is displayed as
+/// its decimal value.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct TlsAlertCode(u8);
+
+impl TlsAlertCode {
+    /// Extracts the TLS alert carried by a QUIC `CRYPTO_ERROR` transport
+    /// error code, if `code` is one.
+    pub fn from_crypto_error(code: u64) -> Option<Self> {
+        code.checked_sub(0x100)
+            .and_then(|alert| u8::try_from(alert).ok())
+            .map(Self)
+    }
+}
+
+impl std::fmt::Display for TlsAlertCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self.0 {
+            0 => "close_notify",
+            10 => "unexpected_message",
+            20 => "bad_record_mac",
+            22 => "record_overflow",
+            40 => "handshake_failure",
+            42 => "bad_certificate",
+            43 => "unsupported_certificate",
+            44 => "certificate_revoked",
+            45 => "certificate_expired",
+            46 => "certificate_unknown",
+            47 => "illegal_parameter",
+            48 => "unknown_ca",
+            49 => "access_denied",
+            50 => "decode_error",
+            51 => "decrypt_error",
+            70 => "protocol_version",
+            71 => "insufficient_security",
+            80 => "internal_error",
+            86 => "inappropriate_fallback",
+            90 => "user_canceled",
+            109 => "missing_extension",
+            110 => "unsupported_extension",
+            112 => "unrecognized_name",
+            113 => "bad_certificate_status_response",
+            115 => "unknown_psk_identity",
+            116 => "certificate_required",
+            120 => "no_application_protocol",
+            code => return write!(f, "{code}"),
+        };
+
+        f.write_str(name)
+    }
+}
+
+impl Serialize for TlsAlertCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl From<u8> for TlsAlertCode {
+    fn from(code: u8) -> Self {
+        Self(code)
+    }
+}
+
 /// QUIC error code (from IANA registry).
 #[derive(Clone, Eq, Hash, PartialEq)]
 pub struct QuicError(u64);
@@ -237,3 +366,364 @@ impl From<u64> for QuicError {
         Self(code)
     }
 }
+
+/// Endpoint that initiated a QUIC stream.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamInitiator {
+    Client,
+    Server,
+}
+
+impl StreamInitiator {
+    /// Derives the initiator from the least significant bit of `stream_id`.
+    pub fn from_stream_id(stream_id: u64) -> Self {
+        if stream_id & 0x1 == 0 {
+            Self::Client
+        } else {
+            Self::Server
+        }
+    }
+}
+
+/// Whether a QUIC stream carries data in one or both directions.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamDirection {
+    Bidirectional,
+    Unidirectional,
+}
+
+impl StreamDirection {
+    /// Derives the direction from the second least significant bit of
+    /// `stream_id`.
+    pub fn from_stream_id(stream_id: u64) -> Self {
+        if stream_id & 0x2 == 0 {
+            Self::Bidirectional
+        } else {
+            Self::Unidirectional
+        }
+    }
+}
+
+/// Class of an HTTP response status code.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+pub enum HttpStatusClass {
+    #[serde(rename = "1xx")]
+    _1xx,
+    #[serde(rename = "2xx")]
+    _2xx,
+    #[serde(rename = "3xx")]
+    _3xx,
+    #[serde(rename = "4xx")]
+    _4xx,
+    #[serde(rename = "5xx")]
+    _5xx,
+}
+
+impl HttpStatusClass {
+    /// Derives the class from a status code, if it is in the range
+    /// `100..=599`.
+    pub fn from_status(status: u16) -> Option<Self> {
+        Some(match status {
+            100..=199 => Self::_1xx,
+            200..=299 => Self::_2xx,
+            300..=399 => Self::_3xx,
+            400..=499 => Self::_4xx,
+            500..=599 => Self::_5xx,
+            _ => return None,
+        })
+    }
+}
+
+/// Flow control limit which stalled a sender.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlowControlDirection {
+    /// Limit of a single stream, raised by `MAX_STREAM_DATA`.
+    Stream,
+    /// Send capacity of the whole connection, which is limited by `MAX_DATA`
+    /// and by the congestion window.
+    Connection,
+}
+
+/// QUIC frame type, reported by its name in RFC 9000.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum QuicFrameType {
+    Padding,
+    Ping,
+    Ack,
+    ResetStream,
+    StopSending,
+    Crypto,
+    NewToken,
+    Stream,
+    MaxData,
+    MaxStreamData,
+    DataBlocked,
+    StreamDataBlocked,
+    NewConnectionId,
+    RetireConnectionId,
+    PathChallenge,
+    PathResponse,
+    ConnectionClose,
+    HandshakeDone,
+    Datagram,
+}
+
+/// Whether a QUIC frame was sent or received.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FrameDirection {
+    Sent,
+    Received,
+}
+
+/// Type of `CONNECTION_CLOSE` frame.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloseFrameType {
+    Transport,
+    Application,
+}
+
+impl From<&quiche::ConnectionError> for CloseFrameType {
+    fn from(err: &quiche::ConnectionError) -> Self {
+        if err.is_app {
+            Self::Application
+        } else {
+            Self::Transport
+        }
+    }
+}
+
+/// Stage reached by a connection attempt, for following connections from
+/// their start to their end.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionAttemptOutcome {
+    /// The connection was created and started its handshake.
+    Started,
+    HandshakeCompleted,
+    HandshakeFailed,
+    /// An established connection was closed by the idle timeout.
+    IdleTimeout,
+    /// An established connection was closed with a transport
+    /// `CONNECTION_CLOSE` frame.
+    TransportError,
+    /// An established connection was closed with an application
+    /// `CONNECTION_CLOSE` frame.
+    ApplicationClose,
+    /// The peer migrated the connection to a new path.
+    MigrationSucceeded,
+}
+
+/// Reason a connection migration failed.
+#[derive(Clone, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationFailureReason {
+    /// The new path could not be validated.
+    ValidationFailed,
+}
+
+/// Category of memory tracked by [`Metrics::memory_usage_bytes`].
+///
+/// quiche 0.23.7 does not report how much memory a connection's stream,
+/// crypto or packet buffers and QPACK tables hold, so only the connection
+/// state itself is tracked.
+///
+/// [`Metrics::memory_usage_bytes`]: super::Metrics::memory_usage_bytes
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryCategory {
+    /// The inline size of each [`quiche::Connection`], excluding the buffers
+    /// and tables it owns.
+    ConnectionState,
+}
+
+/// QUIC version negotiated for a connection.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuicVersion {
+    V1,
+    V2,
+    Draft29,
+    Unknown,
+}
+
+impl From<u32> for QuicVersion {
+    fn from(version: u32) -> Self {
+        match version {
+            0x0000_0001 => Self::V1,
+            0x6b33_43cf => Self::V2,
+            0xff00_001d => Self::Draft29,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Application error code carried by a `RESET_STREAM` frame.
+///
+/// Well-known HTTP/3 error codes are reported by name. Any other code is
+/// reported as `H3_UNKNOWN`, since the code is chosen by the peer.
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct StreamResetReason(u64);
+
+impl Serialize for StreamResetReason {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer
+            .serialize_str(h3_error_code_name(self.0).unwrap_or("H3_UNKNOWN"))
+    }
+}
+
+impl From<u64> for StreamResetReason {
+    fn from(code: u64) -> Self {
+        Self(code)
+    }
+}
+
+/// Maps an HTTP/3 error code to its name in the IANA registry.
+fn h3_error_code_name(code: u64) -> Option<&'static str> {
+    // https://www.iana.org/assignments/http3-parameters/http3-parameters.xhtml
+    if code > 0x21 && (code - 0x21) % 0x1f == 0 {
+        return Some("H3_GREASE");
+    }
+
+    let name = match code {
+        0x33 => "H3_DATAGRAM_ERROR",
+
+        0x100 => "H3_NO_ERROR",
+        0x101 => "H3_GENERAL_PROTOCOL_ERROR",
+        0x102 => "H3_INTERNAL_ERROR",
+        0x103 => "H3_STREAM_CREATION_ERROR",
+        0x104 => "H3_CLOSED_CRITICAL_STREAM",
+        0x105 => "H3_FRAME_UNEXPECTED",
+        0x106 => "H3_FRAME_ERROR",
+        0x107 => "H3_EXCESSIVE_LOAD",
+        0x108 => "H3_ID_ERROR",
+        0x109 => "H3_SETTINGS_ERROR",
+        0x10a => "H3_MISSING_SETTINGS",
+        0x10b => "H3_REQUEST_REJECTED",
+        0x10c => "H3_REQUEST_CANCELLED",
+        0x10d => "H3_REQUEST_INCOMPLETE",
+        0x10e => "H3_MESSAGE_ERROR",
+        0x10f => "H3_CONNECT_ERROR",
+        0x110 => "H3_VERSION_FALLBACK",
+
+        0x200 => "QPACK_DECOMPRESSION_FAILED",
+        0x201 => "QPACK_ENCODER_STREAM_ERROR",
+        0x202 => "QPACK_DECODER_STREAM_ERROR",
+
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_id_classes() {
+        // Client-initiated bidirectional
+        assert!(StreamInitiator::from_stream_id(0) == StreamInitiator::Client);
+        assert!(
+            StreamDirection::from_stream_id(0) == StreamDirection::Bidirectional
+        );
+
+        // Server-initiated bidirectional
+        assert!(StreamInitiator::from_stream_id(5) == StreamInitiator::Server);
+        assert!(
+            StreamDirection::from_stream_id(5) == StreamDirection::Bidirectional
+        );
+
+        // Client-initiated unidirectional
+        assert!(StreamInitiator::from_stream_id(2) == StreamInitiator::Client);
+        assert!(
+            StreamDirection::from_stream_id(2) == StreamDirection::Unidirectional
+        );
+
+        // Server-initiated unidirectional
+        assert!(StreamInitiator::from_stream_id(7) == StreamInitiator::Server);
+        assert!(
+            StreamDirection::from_stream_id(7) == StreamDirection::Unidirectional
+        );
+    }
+
+    #[test]
+    fn stream_reset_reason_unknown_codes_are_collapsed() {
+        let label = |code: u64| {
+            serde_json::to_string(&StreamResetReason::from(code)).unwrap()
+        };
+
+        assert_eq!(label(0x10c), r#""H3_REQUEST_CANCELLED""#);
+        assert_eq!(label(0x21), r#""H3_UNKNOWN""#);
+        assert_eq!(label(u64::MAX), r#""H3_UNKNOWN""#);
+    }
+
+    #[test]
+    fn tls_alert_code_display() {
+        assert_eq!(TlsAlertCode::from(0).to_string(), "close_notify");
+        assert_eq!(TlsAlertCode::from(42).to_string(), "bad_certificate");
+        assert_eq!(
+            TlsAlertCode::from(120).to_string(),
+            "no_application_protocol"
+        );
+        assert_eq!(TlsAlertCode::from(255).to_string(), "255");
+    }
+
+    #[test]
+    fn tls_alert_code_from_crypto_error() {
+        let alert = |code| TlsAlertCode::from_crypto_error(code).map(|a| a.0);
+
+        assert_eq!(alert(0x128), Some(40));
+        assert_eq!(alert(0x1ff), Some(255));
+        assert_eq!(alert(0xa), None);
+        assert_eq!(alert(0x200), None);
+    }
+
+    #[test]
+    fn http_status_class_boundaries() {
+        let class = |status| {
+            HttpStatusClass::from_status(status)
+                .map(|c| serde_json::to_string(&c).unwrap())
+        };
+
+        for (low, high, expected) in [
+            (100, 199, r#""1xx""#),
+            (200, 299, r#""2xx""#),
+            (300, 399, r#""3xx""#),
+            (400, 499, r#""4xx""#),
+            (500, 599, r#""5xx""#),
+        ] {
+            assert_eq!(class(low).as_deref(), Some(expected));
+            assert_eq!(class(high).as_deref(), Some(expected));
+        }
+
+        assert_eq!(class(0), None);
+        assert_eq!(class(99), None);
+        assert_eq!(class(600), None);
+        assert_eq!(class(u16::MAX), None);
+    }
+
+    #[test]
+    fn handshake_abort_reason_from_error() {
+        let reason = |err: BoxError| HandshakeAbortReason::from_error(&err);
+
+        assert!(
+            reason(Box::new(quic::HandshakeError::ConnectionClosed)) ==
+                Some(HandshakeAbortReason::PeerClosed)
+        );
+        assert!(
+            reason(Box::new(quic::HandshakeError::Timeout)) ==
+                Some(HandshakeAbortReason::Timeout)
+        );
+        assert!(
+            reason(Box::new(quiche::Error::InvalidFrame)) ==
+                Some(HandshakeAbortReason::ParseError)
+        );
+        assert!(reason(Box::new(quiche::Error::TlsFail)).is_none());
+    }
+}
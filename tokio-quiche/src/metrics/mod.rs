@@ -26,9 +26,15 @@
 
 //! Metrics collected across QUIC connections.
 
+pub mod aggregator;
+mod buckets;
 pub mod labels;
 pub mod tokio_task;
 
+use self::buckets::bucket_config;
+pub use self::buckets::BucketConfigError;
+pub use self::buckets::MetricsBucketConfig;
+
 use foundations::telemetry::metrics::metrics;
 use foundations::telemetry::metrics::Counter;
 use foundations::telemetry::metrics::Gauge;
@@ -39,10 +45,43 @@ use std::net::IpAddr;
 use std::sync::Arc;
 
 /// Trait to direct the metrics emitted by the crate to a Prometheus registry.
+///
+/// Apart from the original set of metrics, every method has a default
+/// implementation which returns an unregistered handle, so new metrics can be
+/// added without breaking existing implementations.
 pub trait Metrics: Send + Sync + Clone + Unpin + 'static {
     /// Number of QUIC connections currently in memory
     fn connections_in_memory(&self) -> Gauge;
 
+    /// Bytes of memory in use, by category
+    ///
+    /// `ConnectionState` only accounts for the inline size of each
+    /// [`quiche::Connection`], so it is a lower bound on the actual usage.
+    fn memory_usage_bytes(&self, _category: labels::MemoryCategory) -> Gauge {
+        noop::gauge()
+    }
+
+    /// Number of buffers reused from the crate's buffer pools
+    fn buffer_pool_hit_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of buffers newly allocated because a buffer pool was empty
+    fn buffer_pool_miss_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Lifetime of a QUIC connection, from accepting its first Initial packet
+    /// to closing it
+    fn connection_duration_seconds(&self) -> TimeHistogram {
+        noop::time_histogram()
+    }
+
+    /// Number of QUIC connections closed by the idle timeout
+    fn connection_idle_timeout_count(&self) -> Counter {
+        noop::counter()
+    }
+
     /// Maximum number of writable QUIC streams in a connection
     fn maximum_writable_streams(&self) -> Histogram;
 
@@ -54,194 +93,1229 @@ pub trait Metrics: Send + Sync + Clone + Unpin + 'static {
     /// Number of error and partial writes while sending QUIC packets
     fn write_errors(&self, reason: labels::QuicWriteError) -> Counter;
 
-    /// Number of QUIC packets received where the CID could not be verified.
-    fn invalid_cid_packet_count(&self, reason: crate::BoxError) -> Counter;
+    /// Number of QUIC packets received where the CID could not be verified.
+    fn invalid_cid_packet_count(&self, reason: crate::BoxError) -> Counter;
+
+    /// Number of accepted QUIC Initial packets
+    fn accepted_initial_packet_count(&self) -> Counter;
+
+    /// Number of accepted QUIC Initial packets using expensive label(s)
+    fn expensive_accepted_initial_packet_count(&self, peer_ip: IpAddr)
+        -> Counter;
+
+    /// Number of QUIC packets received but not associated with an active
+    /// connection
+    fn rejected_initial_packet_count(
+        &self, reason: labels::QuicInvalidInitialPacketError,
+    ) -> Counter;
+
+    /// Number of new connections rejected because the server is at a resource
+    /// limit
+    fn server_overload_rejection_count(
+        &self, _reason: labels::ServerOverloadReason,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of accepted connections waiting to be picked up from the accept
+    /// queue
+    fn accept_queue_depth(&self) -> Gauge {
+        noop::gauge()
+    }
+
+    /// Number of QUIC packets received but not associated with an active
+    /// connection using expensive label(s)
+    fn expensive_rejected_initial_packet_count(
+        &self, reason: labels::QuicInvalidInitialPacketError, peer_ip: IpAddr,
+    ) -> Counter;
+
+    /// Number of established QUIC connections by TLS server name, using
+    /// expensive label(s)
+    fn sni_count(&self, _sni: Arc<str>) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of QUIC Retry packets sent to validate a client's address
+    fn retry_packet_sent_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of Version Negotiation packets sent in reply to Initials with an
+    /// unsupported QUIC version
+    fn version_negotiation_sent_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of Version Negotiation packets received
+    fn version_negotiation_received_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Time spent validating address tokens of Initial packets
+    fn token_validation_duration(&self) -> TimeHistogram {
+        noop::time_histogram()
+    }
+
+    /// Time from receiving a UDP packet to handing it to its connection's
+    /// IO worker
+    ///
+    /// Only recorded with the `perf-quic-listener-metrics` feature.
+    fn packet_dispatch_latency(&self) -> TimeHistogram {
+        noop::time_histogram()
+    }
+
+    /// Duration of UDP receive system calls which returned a packet
+    ///
+    /// Only recorded with the `perf-quic-listener-metrics` feature.
+    fn udp_recv_syscall_duration(&self) -> TimeHistogram {
+        noop::time_histogram()
+    }
+
+    /// Duration of UDP send system calls which sent data
+    ///
+    /// Only recorded with the `perf-quic-listener-metrics` feature on Linux.
+    fn udp_send_syscall_duration(&self) -> TimeHistogram {
+        noop::time_histogram()
+    }
+
+    /// Combined utilized bandwidth of all open connections (max over the past
+    /// two minutes)
+    fn utilized_bandwidth(&self) -> Gauge;
+
+    /// The highest utilized bandwidh reported during the lifetime of the
+    /// connection
+    fn max_bandwidth_mbps(&self) -> Histogram;
+
+    /// The highest momentary loss reported during the lifetime of the
+    /// connection
+    fn max_loss_pct(&self) -> Histogram;
+
+    /// Combined congestion window of all open connections (max over the past
+    /// round-trip of each connection)
+    fn cwnd_bytes(&self) -> Gauge {
+        noop::gauge()
+    }
+
+    /// Smoothed RTT of QUIC connections, in microseconds
+    fn rtt_micros(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Minimum RTT of QUIC connections over their lifetime, in microseconds
+    fn min_rtt_micros(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Maximum RTT of QUIC connections over their lifetime, in microseconds
+    fn max_rtt_micros(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Number of UDP packets dropped when receiving
+    fn udp_drop_count(&self) -> Counter;
+
+    /// Bytes queued in the send buffers of QUIC listener sockets
+    fn send_buffer_bytes(&self) -> Gauge {
+        noop::gauge()
+    }
+
+    /// Bytes queued in the receive buffers of QUIC listener sockets
+    fn recv_buffer_bytes(&self) -> Gauge {
+        noop::gauge()
+    }
+
+    /// Size of UDP payloads sent by QUIC connections
+    fn sent_packet_size_bytes(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Size of UDP payloads received by QUIC connections
+    fn received_packet_size_bytes(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Number of QUIC packets coalesced into each GSO send
+    fn gso_batch_size(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Ratio of QUIC packets to UDP datagrams in each batch of sent datagrams
+    fn packet_coalescing_ratio(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Number of streams written per call to
+    /// `ApplicationOverQuic::process_writes`
+    fn write_batch_size(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Number of QUIC DATAGRAM frames sent. Not recorded with the
+    /// `gcongestion` feature.
+    fn datagram_send_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of QUIC DATAGRAM frames received. Not recorded with the
+    /// `gcongestion` feature.
+    fn datagram_recv_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of outbound QUIC DATAGRAM frames dropped before being sent
+    fn datagram_drop_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of failed quic handshakes
+    fn failed_handshakes(&self, reason: labels::HandshakeError) -> Counter;
+
+    /// Number of QUIC handshakes aborted for reasons other than a TLS failure
+    fn handshake_abort_reason_count(
+        &self, _reason: labels::HandshakeAbortReason,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of TLS alerts sent or received during QUIC handshakes
+    fn tls_alert_count(&self, _code: labels::TlsAlertCode) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of successful quic handshakes
+    fn handshake_success_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of client connections that sent 0-RTT packets
+    fn zero_rtt_attempt_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of server connections that accepted 0-RTT data
+    fn zero_rtt_accepted_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of connections whose 0-RTT data was rejected
+    ///
+    /// Servers count connections that received 0-RTT packets without
+    /// accepting early data. quiche doesn't report the server's decision to
+    /// clients, so clients only count connections that sent 0-RTT packets but
+    /// did not resume the TLS session. Early data rejected on a resumed
+    /// session is not counted by the client.
+    fn zero_rtt_rejected_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of certificates in the peer's certificate chain, recorded once
+    /// per connection when the TLS handshake completes
+    fn certificate_chain_depth(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Bytes of 0-RTT packets received by a server before the handshake
+    /// completed, for connections which accepted early data
+    fn early_data_size_bytes(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Number of QUIC packets sent or received until the handshake completed
+    fn handshake_packet_count(
+        &self, _direction: labels::FrameDirection,
+    ) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Number of active source connection IDs of a connection, sampled every
+    /// second
+    fn active_connection_id_count(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Number of client handshakes that attempted to resume a TLS session
+    fn tls_session_resumption_attempted(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of client handshakes that successfully resumed a TLS session
+    fn tls_session_resumption_succeeded(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Packet loss percentage of a connection, sampled at a fixed interval over
+    /// the connection's lifetime
+    fn packet_loss_rate_pct(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Time spent waiting for the UDP socket to become writable again after the
+    /// kernel send buffer filled up
+    fn write_blocked_duration(&self) -> TimeHistogram {
+        noop::time_histogram()
+    }
+
+    /// Time an HTTP/3 stream spent waiting for more body data from the peer
+    /// after all received data was handed to the application
+    fn read_blocked_duration(&self) -> TimeHistogram {
+        noop::time_histogram()
+    }
+
+    /// Number of connection attempts that failed before a handshake was started
+    fn connection_error_before_handshake_count(
+        &self, _reason: labels::PreHandshakeError,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of stream data bytes sent again after the packet carrying them
+    /// was declared lost
+    fn bytes_retransmitted(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Bandwidth estimate of a connection in Mbps, sampled every second
+    fn bandwidth_estimate_mbps(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Time between the completion of the handshake and the opening of the
+    /// first HTTP/3 request stream on a connection
+    fn connection_age_at_first_stream(&self) -> TimeHistogram {
+        noop::time_histogram()
+    }
+
+    /// Number of HTTP/3 request streams a connection carried before it was
+    /// closed
+    fn streams_per_connection_histogram(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Size of HTTP/3 request bodies
+    fn h3_request_body_size_bytes(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Size of HTTP/3 response bodies
+    fn h3_response_body_size_bytes(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Time between receiving an HTTP/3 request and sending the first frame of
+    /// its response
+    fn h3_time_to_first_byte(&self) -> TimeHistogram {
+        noop::time_histogram()
+    }
+
+    /// Time between receiving the last byte of an HTTP/3 request and the peer
+    /// acknowledging the last byte of its response
+    fn h3_total_request_duration(&self) -> TimeHistogram {
+        noop::time_histogram()
+    }
+
+    /// Number of stream bytes written by the application which have not been
+    /// accepted by the QUIC connection for transmission yet, sampled once per
+    /// second
+    fn pending_send_data_bytes(&self) -> Gauge {
+        noop::gauge()
+    }
+
+    /// Maximum ACK delay advertised by the peer in its transport parameters
+    fn max_ack_delay_micros(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Number of connection attempts which reached each stage, from being
+    /// started to being closed
+    fn connection_attempt_count(
+        &self, _outcome: labels::ConnectionAttemptOutcome,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of received packets dropped because the connection's incoming
+    /// packet queue was full
+    fn receive_queue_overflow_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of connections created with an IPv4 peer
+    fn ipv4_connection_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of connections created with an IPv6 peer
+    fn ipv6_connection_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of connections migrated by the peer to a validated path
+    fn connection_migration_success_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of failed connection migrations
+    fn connection_migration_failure_count(
+        &self, _reason: labels::MigrationFailureReason,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Time from sending a PATH_CHALLENGE on a new path to receiving the
+    /// matching PATH_RESPONSE
+    fn path_validation_duration(&self) -> TimeHistogram {
+        noop::time_histogram()
+    }
+
+    /// Number of QUIC frames, by frame type. DATAGRAM frames are not counted
+    /// with the `gcongestion` feature.
+    fn frame_count(
+        &self, _frame_type: labels::QuicFrameType,
+        _direction: labels::FrameDirection,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of `CONNECTION_CLOSE` frames, by whether they carry a transport
+    /// or application error
+    fn connection_close_frame_type_count(
+        &self, _frame_type: labels::CloseFrameType,
+        _direction: labels::FrameDirection,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of HTTP/3 requests received
+    fn h3_request_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of HTTP/3 requests sent on a QUIC connection that already carried
+    /// a request
+    fn connection_reuse_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of HTTP/3 responses sent, by status class
+    fn h3_response_status_count(
+        &self, _status_class: labels::HttpStatusClass,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of HTTP/3 GOAWAY frames sent
+    fn h3_goaway_sent_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of HTTP/3 GOAWAY frames received
+    fn h3_goaway_received_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of HTTP/3 SETTINGS frames sent
+    fn h3_settings_sent_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of HTTP/3 SETTINGS frames received
+    fn h3_settings_received_count(&self) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of HTTP/3 unidirectional streams opened by either endpoint, by
+    /// stream type. Only control streams are counted; see
+    /// [`labels::H3StreamType`].
+    fn h3_unidirectional_stream_opened(
+        &self, _stream_type: labels::H3StreamType,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of accepted connections by negotiated QUIC version
+    fn negotiated_version_count(&self, _version: labels::QuicVersion) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of HTTP/3 connection closures generated locally
+    fn local_h3_conn_close_error_count(&self, reason: labels::H3Error)
+        -> Counter;
+
+    /// Number of HTTP/3 connection closures generated locally due to a QPACK
+    /// error
+    fn local_qpack_conn_close_error_count(
+        &self, _reason: labels::QpackError,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of QUIC connection closures generated locally
+    fn local_quic_conn_close_error_count(
+        &self, reason: labels::QuicError,
+    ) -> Counter;
+
+    /// Number of HTTP/3 connection closures generated by peer
+    fn peer_h3_conn_close_error_count(&self, reason: labels::H3Error) -> Counter;
+
+    /// Number of HTTP/3 connection closures generated by peer due to a QPACK
+    /// error
+    fn peer_qpack_conn_close_error_count(
+        &self, _reason: labels::QpackError,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of QUIC connection closures generated by peer
+    fn peer_quic_conn_close_error_count(
+        &self, reason: labels::QuicError,
+    ) -> Counter;
+
+    /// Number of bytes sent on a QUIC stream over its lifetime
+    fn stream_bytes_sent(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Number of bytes received on a QUIC stream over its lifetime
+    fn stream_bytes_received(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// Number of QUIC streams opened
+    fn streams_opened(
+        &self, _initiator: labels::StreamInitiator,
+        _direction: labels::StreamDirection,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of QUIC streams closed
+    fn streams_closed(
+        &self, _initiator: labels::StreamInitiator,
+        _direction: labels::StreamDirection,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of QUIC streams currently open across all connections
+    fn open_streams_gauge(&self) -> Gauge {
+        noop::gauge()
+    }
+
+    /// Number of QUIC streams reset by the peer
+    fn stream_reset_count(&self, _reason: labels::StreamResetReason) -> Counter {
+        noop::counter()
+    }
+
+    /// Number of QUIC streams reset locally
+    fn peer_stream_reset_count(
+        &self, _reason: labels::StreamResetReason,
+    ) -> Counter {
+        noop::counter()
+    }
+
+    /// Time QUIC streams spent blocked on flow control
+    fn stream_flow_control_blocked_duration(
+        &self, _direction: labels::FlowControlDirection,
+    ) -> TimeHistogram {
+        noop::time_histogram()
+    }
+
+    /// Ratio of QPACK-encoded to uncompressed size of sent HTTP/3 headers,
+    /// sampled from one in 16 sent HEADERS frames
+    fn h3_header_compression_ratio(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    /// SETTINGS_MAX_FIELD_SECTION_SIZE advertised by peers, in bytes
+    fn h3_settings_max_field_section_size_histogram(&self) -> Histogram {
+        noop::histogram()
+    }
+
+    // ==== tokio runtime metrics ====
+
+    /// Histogram of task schedule delays
+    fn tokio_runtime_task_schedule_delay_histogram(
+        &self, task: &Arc<str>,
+    ) -> TimeHistogram;
+
+    /// Histogram of task poll durations
+    fn tokio_runtime_task_poll_duration_histogram(
+        &self, task: &Arc<str>,
+    ) -> TimeHistogram;
+
+    /// Helps us get a rough idea of if our waker is causing issues.
+    fn tokio_runtime_task_total_poll_time_micros(
+        &self, task: &Arc<str>,
+    ) -> Counter;
+
+    /// Number of times a task was woken
+    fn tokio_runtime_task_wakeup_count(&self, _task: &Arc<str>) -> Counter {
+        noop::counter()
+    }
+
+    /// CPU time spent by each runtime thread polling instrumented tasks
+    ///
+    /// Threads are labelled with a dense index, assigned in the order in which
+    /// they first poll an instrumented task.
+    fn worker_thread_cpu_time_micros(&self, _worker: usize) -> Counter {
+        noop::counter()
+    }
+}
+
+/// Standard implementation of [`Metrics`] using
+/// [`foundations::telemetry::metrics`].
+#[derive(Default, Clone)]
+pub struct DefaultMetrics;
+
+impl DefaultMetrics {
+    /// Creates a [`DefaultMetrics`] whose histograms use the bucket boundaries
+    /// in `config` instead of the default ones.
+    ///
+    /// Bucket boundaries are shared by the whole process and are fixed once the
+    /// first metric has been created, so this should be called at startup.
+    ///
+    /// # Errors
+    /// Fails if the buckets of a histogram are not in ascending order, or if
+    /// the boundaries have already been fixed.
+    pub fn with_bucket_config(
+        config: MetricsBucketConfig,
+    ) -> Result<Self, BucketConfigError> {
+        config.install().map(|()| Self)
+    }
+}
+
+impl Metrics for DefaultMetrics {
+    fn connections_in_memory(&self) -> Gauge {
+        quic::connections_in_memory()
+    }
+
+    fn memory_usage_bytes(&self, category: labels::MemoryCategory) -> Gauge {
+        quic::memory_usage_bytes(category)
+    }
+
+    fn buffer_pool_hit_count(&self) -> Counter {
+        quic::buffer_pool_hit_count()
+    }
+
+    fn buffer_pool_miss_count(&self) -> Counter {
+        quic::buffer_pool_miss_count()
+    }
+
+    fn connection_duration_seconds(&self) -> TimeHistogram {
+        quic::connection_duration_seconds()
+    }
+
+    fn connection_idle_timeout_count(&self) -> Counter {
+        quic::connection_idle_timeout_count()
+    }
+
+    fn maximum_writable_streams(&self) -> Histogram {
+        quic::maximum_writable_streams()
+    }
+
+    fn handshake_time_seconds(
+        &self, stage: labels::QuicHandshakeStage,
+    ) -> TimeHistogram {
+        quic::handshake_time_seconds(stage)
+    }
+
+    fn write_errors(&self, reason: labels::QuicWriteError) -> Counter {
+        quic::write_errors(reason)
+    }
+
+    fn invalid_cid_packet_count(&self, reason: crate::BoxError) -> Counter {
+        quic::invalid_cid_packet_count(reason.to_string())
+    }
+
+    fn accepted_initial_packet_count(&self) -> Counter {
+        quic::accepted_initial_packet_count()
+    }
+
+    fn expensive_accepted_initial_packet_count(
+        &self, peer_ip: IpAddr,
+    ) -> Counter {
+        quic::expensive_accepted_initial_packet_count(peer_ip)
+    }
+
+    fn rejected_initial_packet_count(
+        &self, reason: labels::QuicInvalidInitialPacketError,
+    ) -> Counter {
+        quic::rejected_initial_packet_count(reason)
+    }
+
+    fn server_overload_rejection_count(
+        &self, reason: labels::ServerOverloadReason,
+    ) -> Counter {
+        quic::server_overload_rejection_count(reason)
+    }
+
+    fn accept_queue_depth(&self) -> Gauge {
+        quic::accept_queue_depth()
+    }
+
+    fn expensive_rejected_initial_packet_count(
+        &self, reason: labels::QuicInvalidInitialPacketError, peer_ip: IpAddr,
+    ) -> Counter {
+        quic::expensive_rejected_initial_packet_count(reason, peer_ip)
+    }
+
+    fn sni_count(&self, sni: Arc<str>) -> Counter {
+        quic::sni_count(sni)
+    }
+
+    fn retry_packet_sent_count(&self) -> Counter {
+        quic::retry_packet_sent_count()
+    }
+
+    fn version_negotiation_sent_count(&self) -> Counter {
+        quic::version_negotiation_sent_count()
+    }
+
+    fn version_negotiation_received_count(&self) -> Counter {
+        quic::version_negotiation_received_count()
+    }
+
+    fn token_validation_duration(&self) -> TimeHistogram {
+        quic::token_validation_duration()
+    }
+
+    fn packet_dispatch_latency(&self) -> TimeHistogram {
+        quic::packet_dispatch_latency()
+    }
+
+    fn udp_recv_syscall_duration(&self) -> TimeHistogram {
+        quic::udp_recv_syscall_duration()
+    }
+
+    fn udp_send_syscall_duration(&self) -> TimeHistogram {
+        quic::udp_send_syscall_duration()
+    }
+
+    fn utilized_bandwidth(&self) -> Gauge {
+        quic::utilized_bandwidth()
+    }
+
+    fn max_bandwidth_mbps(&self) -> Histogram {
+        quic::max_bandwidth_mbps()
+    }
+
+    fn max_loss_pct(&self) -> Histogram {
+        quic::max_loss_pct()
+    }
+
+    fn cwnd_bytes(&self) -> Gauge {
+        quic::cwnd_bytes()
+    }
+
+    fn rtt_micros(&self) -> Histogram {
+        quic::rtt_micros()
+    }
+
+    fn min_rtt_micros(&self) -> Histogram {
+        quic::min_rtt_micros()
+    }
+
+    fn max_rtt_micros(&self) -> Histogram {
+        quic::max_rtt_micros()
+    }
+
+    fn udp_drop_count(&self) -> Counter {
+        quic::udp_drop_count()
+    }
+
+    fn send_buffer_bytes(&self) -> Gauge {
+        quic::send_buffer_bytes()
+    }
+
+    fn recv_buffer_bytes(&self) -> Gauge {
+        quic::recv_buffer_bytes()
+    }
+
+    fn sent_packet_size_bytes(&self) -> Histogram {
+        quic::sent_packet_size_bytes()
+    }
+
+    fn received_packet_size_bytes(&self) -> Histogram {
+        quic::received_packet_size_bytes()
+    }
+
+    fn gso_batch_size(&self) -> Histogram {
+        quic::gso_batch_size()
+    }
+
+    fn packet_coalescing_ratio(&self) -> Histogram {
+        quic::packet_coalescing_ratio()
+    }
+
+    fn write_batch_size(&self) -> Histogram {
+        quic::write_batch_size()
+    }
+
+    fn datagram_send_count(&self) -> Counter {
+        quic::datagram_send_count()
+    }
+
+    fn datagram_recv_count(&self) -> Counter {
+        quic::datagram_recv_count()
+    }
+
+    fn datagram_drop_count(&self) -> Counter {
+        quic::datagram_drop_count()
+    }
+
+    fn failed_handshakes(&self, reason: labels::HandshakeError) -> Counter {
+        quic::failed_handshakes(reason)
+    }
+
+    fn handshake_abort_reason_count(
+        &self, reason: labels::HandshakeAbortReason,
+    ) -> Counter {
+        quic::handshake_abort_reason_count(reason)
+    }
+
+    fn tls_alert_count(&self, code: labels::TlsAlertCode) -> Counter {
+        quic::tls_alert_count(code)
+    }
+
+    fn handshake_success_count(&self) -> Counter {
+        quic::handshake_success_count()
+    }
+
+    fn zero_rtt_attempt_count(&self) -> Counter {
+        quic::zero_rtt_attempt_count()
+    }
+
+    fn zero_rtt_accepted_count(&self) -> Counter {
+        quic::zero_rtt_accepted_count()
+    }
+
+    fn zero_rtt_rejected_count(&self) -> Counter {
+        quic::zero_rtt_rejected_count()
+    }
+
+    fn certificate_chain_depth(&self) -> Histogram {
+        quic::certificate_chain_depth()
+    }
+
+    fn early_data_size_bytes(&self) -> Histogram {
+        quic::early_data_size_bytes()
+    }
+
+    fn handshake_packet_count(
+        &self, direction: labels::FrameDirection,
+    ) -> Histogram {
+        quic::handshake_packet_count(direction)
+    }
+
+    fn active_connection_id_count(&self) -> Histogram {
+        quic::active_connection_id_count()
+    }
+
+    fn tls_session_resumption_attempted(&self) -> Counter {
+        quic::tls_session_resumption_attempted()
+    }
+
+    fn tls_session_resumption_succeeded(&self) -> Counter {
+        quic::tls_session_resumption_succeeded()
+    }
+
+    fn packet_loss_rate_pct(&self) -> Histogram {
+        quic::packet_loss_rate_pct()
+    }
+
+    fn write_blocked_duration(&self) -> TimeHistogram {
+        quic::write_blocked_duration()
+    }
+
+    fn read_blocked_duration(&self) -> TimeHistogram {
+        quic::read_blocked_duration()
+    }
+
+    fn connection_error_before_handshake_count(
+        &self, reason: labels::PreHandshakeError,
+    ) -> Counter {
+        quic::connection_error_before_handshake_count(reason)
+    }
+
+    fn bytes_retransmitted(&self) -> Counter {
+        quic::bytes_retransmitted()
+    }
+
+    fn bandwidth_estimate_mbps(&self) -> Histogram {
+        quic::bandwidth_estimate_mbps()
+    }
+
+    fn connection_age_at_first_stream(&self) -> TimeHistogram {
+        quic::connection_age_at_first_stream()
+    }
+
+    fn streams_per_connection_histogram(&self) -> Histogram {
+        quic::streams_per_connection_histogram()
+    }
+
+    fn h3_request_body_size_bytes(&self) -> Histogram {
+        quic::h3_request_body_size_bytes()
+    }
+
+    fn h3_response_body_size_bytes(&self) -> Histogram {
+        quic::h3_response_body_size_bytes()
+    }
+
+    fn h3_time_to_first_byte(&self) -> TimeHistogram {
+        quic::h3_time_to_first_byte()
+    }
+
+    fn h3_total_request_duration(&self) -> TimeHistogram {
+        quic::h3_total_request_duration()
+    }
+
+    fn pending_send_data_bytes(&self) -> Gauge {
+        quic::pending_send_data_bytes()
+    }
+
+    fn max_ack_delay_micros(&self) -> Histogram {
+        quic::max_ack_delay_micros()
+    }
+
+    fn connection_attempt_count(
+        &self, outcome: labels::ConnectionAttemptOutcome,
+    ) -> Counter {
+        quic::connection_attempt_count(outcome)
+    }
+
+    fn receive_queue_overflow_count(&self) -> Counter {
+        quic::receive_queue_overflow_count()
+    }
+
+    fn ipv4_connection_count(&self) -> Counter {
+        quic::ipv4_connection_count()
+    }
+
+    fn ipv6_connection_count(&self) -> Counter {
+        quic::ipv6_connection_count()
+    }
+
+    fn connection_migration_success_count(&self) -> Counter {
+        quic::connection_migration_success_count()
+    }
+
+    fn connection_migration_failure_count(
+        &self, reason: labels::MigrationFailureReason,
+    ) -> Counter {
+        quic::connection_migration_failure_count(reason)
+    }
+
+    fn path_validation_duration(&self) -> TimeHistogram {
+        quic::path_validation_duration()
+    }
+
+    fn frame_count(
+        &self, frame_type: labels::QuicFrameType,
+        direction: labels::FrameDirection,
+    ) -> Counter {
+        quic::frame_count(frame_type, direction)
+    }
+
+    fn connection_close_frame_type_count(
+        &self, frame_type: labels::CloseFrameType,
+        direction: labels::FrameDirection,
+    ) -> Counter {
+        quic::connection_close_frame_type_count(frame_type, direction)
+    }
+
+    fn h3_request_count(&self) -> Counter {
+        quic::h3_request_count()
+    }
+
+    fn connection_reuse_count(&self) -> Counter {
+        quic::connection_reuse_count()
+    }
+
+    fn h3_response_status_count(
+        &self, status_class: labels::HttpStatusClass,
+    ) -> Counter {
+        quic::h3_response_status_count(status_class)
+    }
+
+    fn h3_goaway_sent_count(&self) -> Counter {
+        quic::h3_goaway_sent_count()
+    }
+
+    fn h3_goaway_received_count(&self) -> Counter {
+        quic::h3_goaway_received_count()
+    }
+
+    fn h3_settings_sent_count(&self) -> Counter {
+        quic::h3_settings_sent_count()
+    }
+
+    fn h3_settings_received_count(&self) -> Counter {
+        quic::h3_settings_received_count()
+    }
+
+    fn h3_unidirectional_stream_opened(
+        &self, stream_type: labels::H3StreamType,
+    ) -> Counter {
+        quic::h3_unidirectional_stream_opened(stream_type)
+    }
+
+    fn negotiated_version_count(&self, version: labels::QuicVersion) -> Counter {
+        quic::negotiated_version_count(version)
+    }
+
+    fn local_h3_conn_close_error_count(
+        &self, reason: labels::H3Error,
+    ) -> Counter {
+        quic::local_h3_conn_close_error_count(reason)
+    }
+
+    fn local_qpack_conn_close_error_count(
+        &self, reason: labels::QpackError,
+    ) -> Counter {
+        quic::local_qpack_conn_close_error_count(reason)
+    }
+
+    fn local_quic_conn_close_error_count(
+        &self, reason: labels::QuicError,
+    ) -> Counter {
+        quic::local_quic_conn_close_error_count(reason)
+    }
 
-    /// Number of accepted QUIC Initial packets
-    fn accepted_initial_packet_count(&self) -> Counter;
+    fn peer_h3_conn_close_error_count(&self, reason: labels::H3Error) -> Counter {
+        quic::peer_h3_conn_close_error_count(reason)
+    }
 
-    /// Number of accepted QUIC Initial packets using expensive label(s)
-    fn expensive_accepted_initial_packet_count(&self, peer_ip: IpAddr)
-        -> Counter;
+    fn peer_qpack_conn_close_error_count(
+        &self, reason: labels::QpackError,
+    ) -> Counter {
+        quic::peer_qpack_conn_close_error_count(reason)
+    }
 
-    /// Number of QUIC packets received but not associated with an active
-    /// connection
-    fn rejected_initial_packet_count(
-        &self, reason: labels::QuicInvalidInitialPacketError,
-    ) -> Counter;
+    fn peer_quic_conn_close_error_count(
+        &self, reason: labels::QuicError,
+    ) -> Counter {
+        quic::peer_quic_conn_close_error_count(reason)
+    }
 
-    /// Number of QUIC packets received but not associated with an active
-    /// connection using expensive label(s)
-    fn expensive_rejected_initial_packet_count(
-        &self, reason: labels::QuicInvalidInitialPacketError, peer_ip: IpAddr,
-    ) -> Counter;
+    fn stream_bytes_sent(&self) -> Histogram {
+        quic::stream_bytes_sent()
+    }
 
-    /// Combined utilized bandwidth of all open connections (max over the past
-    /// two minutes)
-    fn utilized_bandwidth(&self) -> Gauge;
+    fn stream_bytes_received(&self) -> Histogram {
+        quic::stream_bytes_received()
+    }
 
-    /// The highest utilized bandwidh reported during the lifetime of the
-    /// connection
-    fn max_bandwidth_mbps(&self) -> Histogram;
+    fn streams_opened(
+        &self, initiator: labels::StreamInitiator,
+        direction: labels::StreamDirection,
+    ) -> Counter {
+        quic::streams_opened(initiator, direction)
+    }
 
-    /// The highest momentary loss reported during the lifetime of the
-    /// connection
-    fn max_loss_pct(&self) -> Histogram;
+    fn streams_closed(
+        &self, initiator: labels::StreamInitiator,
+        direction: labels::StreamDirection,
+    ) -> Counter {
+        quic::streams_closed(initiator, direction)
+    }
 
-    /// Number of UDP packets dropped when receiving
-    fn udp_drop_count(&self) -> Counter;
+    fn open_streams_gauge(&self) -> Gauge {
+        quic::open_streams()
+    }
 
-    /// Number of failed quic handshakes
-    fn failed_handshakes(&self, reason: labels::HandshakeError) -> Counter;
+    fn stream_reset_count(&self, reason: labels::StreamResetReason) -> Counter {
+        quic::stream_reset_count(reason)
+    }
 
-    /// Number of HTTP/3 connection closures generated locally
-    fn local_h3_conn_close_error_count(&self, reason: labels::H3Error)
-        -> Counter;
+    fn peer_stream_reset_count(
+        &self, reason: labels::StreamResetReason,
+    ) -> Counter {
+        quic::peer_stream_reset_count(reason)
+    }
 
-    /// Number of QUIC connection closures generated locally
-    fn local_quic_conn_close_error_count(
-        &self, reason: labels::QuicError,
-    ) -> Counter;
+    fn stream_flow_control_blocked_duration(
+        &self, direction: labels::FlowControlDirection,
+    ) -> TimeHistogram {
+        quic::stream_flow_control_blocked_duration(direction)
+    }
 
-    /// Number of HTTP/3 connection closures generated by peer
-    fn peer_h3_conn_close_error_count(&self, reason: labels::H3Error) -> Counter;
+    fn h3_header_compression_ratio(&self) -> Histogram {
+        quic::h3_header_compression_ratio()
+    }
 
-    /// Number of QUIC connection closures generated by peer
-    fn peer_quic_conn_close_error_count(
-        &self, reason: labels::QuicError,
-    ) -> Counter;
+    fn h3_settings_max_field_section_size_histogram(&self) -> Histogram {
+        quic::h3_settings_max_field_section_size_histogram()
+    }
 
     // ==== tokio runtime metrics ====
 
     /// Histogram of task schedule delays
     fn tokio_runtime_task_schedule_delay_histogram(
         &self, task: &Arc<str>,
-    ) -> TimeHistogram;
+    ) -> TimeHistogram {
+        tokio::runtime_task_schedule_delay_histogram(task)
+    }
 
     /// Histogram of task poll durations
     fn tokio_runtime_task_poll_duration_histogram(
         &self, task: &Arc<str>,
-    ) -> TimeHistogram;
+    ) -> TimeHistogram {
+        tokio::runtime_task_poll_duration_histogram(task)
+    }
 
     /// Helps us get a rough idea of if our waker is causing issues.
     fn tokio_runtime_task_total_poll_time_micros(
         &self, task: &Arc<str>,
-    ) -> Counter;
+    ) -> Counter {
+        tokio::runtime_task_total_poll_time_micros(task)
+    }
+
+    /// Number of times a task was woken
+    fn tokio_runtime_task_wakeup_count(&self, task: &Arc<str>) -> Counter {
+        tokio::runtime_task_wakeup_count(task)
+    }
+
+    fn worker_thread_cpu_time_micros(&self, worker: usize) -> Counter {
+        tokio::worker_thread_cpu_time_micros(worker)
+    }
 }
 
-/// Standard implementation of [`Metrics`] using
-/// [`foundations::telemetry::metrics`].
+/// Implementation of [`Metrics`] which discards all observations.
+///
+/// Every method returns a fresh handle which is not registered anywhere. This
+/// is useful for tests and for embedding the crate without a metrics registry.
 #[derive(Default, Clone)]
-pub struct DefaultMetrics;
+pub struct NullMetrics;
 
-impl Metrics for DefaultMetrics {
+impl Metrics for NullMetrics {
     fn connections_in_memory(&self) -> Gauge {
-        quic::connections_in_memory()
+        noop::gauge()
     }
 
     fn maximum_writable_streams(&self) -> Histogram {
-        quic::maximum_writable_streams()
+        noop::histogram()
     }
 
     fn handshake_time_seconds(
-        &self, stage: labels::QuicHandshakeStage,
+        &self, _stage: labels::QuicHandshakeStage,
     ) -> TimeHistogram {
-        quic::handshake_time_seconds(stage)
+        noop::time_histogram()
     }
 
-    fn write_errors(&self, reason: labels::QuicWriteError) -> Counter {
-        quic::write_errors(reason)
+    fn write_errors(&self, _reason: labels::QuicWriteError) -> Counter {
+        noop::counter()
     }
 
-    fn invalid_cid_packet_count(&self, reason: crate::BoxError) -> Counter {
-        quic::invalid_cid_packet_count(reason.to_string())
+    fn invalid_cid_packet_count(&self, _reason: crate::BoxError) -> Counter {
+        noop::counter()
     }
 
     fn accepted_initial_packet_count(&self) -> Counter {
-        quic::accepted_initial_packet_count()
+        noop::counter()
     }
 
     fn expensive_accepted_initial_packet_count(
-        &self, peer_ip: IpAddr,
+        &self, _peer_ip: IpAddr,
     ) -> Counter {
-        quic::expensive_accepted_initial_packet_count(peer_ip)
+        noop::counter()
     }
 
     fn rejected_initial_packet_count(
-        &self, reason: labels::QuicInvalidInitialPacketError,
+        &self, _reason: labels::QuicInvalidInitialPacketError,
     ) -> Counter {
-        quic::rejected_initial_packet_count(reason)
+        noop::counter()
     }
 
     fn expensive_rejected_initial_packet_count(
-        &self, reason: labels::QuicInvalidInitialPacketError, peer_ip: IpAddr,
+        &self, _reason: labels::QuicInvalidInitialPacketError, _peer_ip: IpAddr,
     ) -> Counter {
-        quic::expensive_rejected_initial_packet_count(reason, peer_ip)
+        noop::counter()
     }
 
     fn utilized_bandwidth(&self) -> Gauge {
-        quic::utilized_bandwidth()
+        noop::gauge()
     }
 
     fn max_bandwidth_mbps(&self) -> Histogram {
-        quic::max_bandwidth_mbps()
+        noop::histogram()
     }
 
     fn max_loss_pct(&self) -> Histogram {
-        quic::max_loss_pct()
+        noop::histogram()
     }
 
     fn udp_drop_count(&self) -> Counter {
-        quic::udp_drop_count()
+        noop::counter()
     }
 
-    fn failed_handshakes(&self, reason: labels::HandshakeError) -> Counter {
-        quic::failed_handshakes(reason)
+    fn failed_handshakes(&self, _reason: labels::HandshakeError) -> Counter {
+        noop::counter()
     }
 
     fn local_h3_conn_close_error_count(
-        &self, reason: labels::H3Error,
+        &self, _reason: labels::H3Error,
     ) -> Counter {
-        quic::local_h3_conn_close_error_count(reason)
+        noop::counter()
     }
 
     fn local_quic_conn_close_error_count(
-        &self, reason: labels::QuicError,
+        &self, _reason: labels::QuicError,
     ) -> Counter {
-        quic::local_quic_conn_close_error_count(reason)
+        noop::counter()
     }
 
-    fn peer_h3_conn_close_error_count(&self, reason: labels::H3Error) -> Counter {
-        quic::peer_h3_conn_close_error_count(reason)
+    fn peer_h3_conn_close_error_count(
+        &self, _reason: labels::H3Error,
+    ) -> Counter {
+        noop::counter()
     }
 
     fn peer_quic_conn_close_error_count(
-        &self, reason: labels::QuicError,
+        &self, _reason: labels::QuicError,
     ) -> Counter {
-        quic::peer_quic_conn_close_error_count(reason)
+        noop::counter()
     }
 
     // ==== tokio runtime metrics ====
 
-    /// Histogram of task schedule delays
     fn tokio_runtime_task_schedule_delay_histogram(
-        &self, task: &Arc<str>,
+        &self, _task: &Arc<str>,
     ) -> TimeHistogram {
-        tokio::runtime_task_schedule_delay_histogram(task)
+        noop::time_histogram()
     }
 
-    /// Histogram of task poll durations
     fn tokio_runtime_task_poll_duration_histogram(
-        &self, task: &Arc<str>,
+        &self, _task: &Arc<str>,
     ) -> TimeHistogram {
-        tokio::runtime_task_poll_duration_histogram(task)
+        noop::time_histogram()
     }
 
-    /// Helps us get a rough idea of if our waker is causing issues.
     fn tokio_runtime_task_total_poll_time_micros(
-        &self, task: &Arc<str>,
+        &self, _task: &Arc<str>,
     ) -> Counter {
-        tokio::runtime_task_total_poll_time_micros(task)
+        noop::counter()
+    }
+}
+
+/// Unregistered metric handles returned by [`NullMetrics`] and by the default
+/// implementations of [`Metrics`] methods.
+mod noop {
+    use super::*;
+
+    pub(super) fn counter() -> Counter {
+        Counter::default()
+    }
+
+    pub(super) fn gauge() -> Gauge {
+        Gauge::default()
+    }
+
+    pub(super) fn histogram() -> Histogram {
+        Histogram::new(std::iter::empty())
+    }
+
+    pub(super) fn time_histogram() -> TimeHistogram {
+        TimeHistogram::new(std::iter::empty())
     }
 }
 
@@ -250,13 +1324,33 @@ pub(crate) mod quic {
     /// Number of QUIC connections currently in memory
     pub fn connections_in_memory() -> Gauge;
 
+    /// Bytes of memory in use, by category
+    ///
+    /// `ConnectionState` only accounts for the inline size of each
+    /// [`quiche::Connection`], so it is a lower bound on the actual usage.
+    pub fn memory_usage_bytes(category: labels::MemoryCategory) -> Gauge;
+
+    /// Number of buffers reused from the crate's buffer pools
+    pub fn buffer_pool_hit_count() -> Counter;
+
+    /// Number of buffers newly allocated because a buffer pool was empty
+    pub fn buffer_pool_miss_count() -> Counter;
+
+    /// Lifetime of a QUIC connection, from accepting its first Initial packet
+    /// to closing it
+    #[ctor = HistogramBuilder { buckets: bucket_config().connection_duration_seconds, }]
+    pub fn connection_duration_seconds() -> TimeHistogram;
+
+    /// Number of QUIC connections closed by the idle timeout
+    pub fn connection_idle_timeout_count() -> Counter;
+
     /// Maximum number of writable QUIC streams in a connection
     #[optional]
-    #[ctor = HistogramBuilder { buckets: &[0.0, 5.0, 10.0, 100.0, 1000.0, 2000.0, 3000.0, 10000.0, 20000.0, 50000.0], }]
+    #[ctor = HistogramBuilder { buckets: bucket_config().maximum_writable_streams, }]
     pub fn maximum_writable_streams() -> Histogram;
 
     /// Overhead of QUIC handshake processing stage
-    #[ctor = HistogramBuilder { buckets: &[1E-5, 2E-5, 5E-5, 1E-4, 2E-4, 5E-4, 1E-3, 2E-3, 5E-3, 1E-2, 2E-2, 5E-2, 0.1, 0.2, 0.5, 1.0, 2.0, 5.0], }]
+    #[ctor = HistogramBuilder { buckets: bucket_config().handshake_time_seconds, }]
     pub fn handshake_time_seconds(
         stage: labels::QuicHandshakeStage,
     ) -> TimeHistogram;
@@ -280,6 +1374,16 @@ pub(crate) mod quic {
         reason: labels::QuicInvalidInitialPacketError,
     ) -> Counter;
 
+    /// Number of new connections rejected because the server is at a resource
+    /// limit
+    pub fn server_overload_rejection_count(
+        reason: labels::ServerOverloadReason,
+    ) -> Counter;
+
+    /// Number of accepted connections waiting to be picked up from the accept
+    /// queue
+    pub fn accept_queue_depth() -> Gauge;
+
     /// Number of QUIC packets received but not associated with an active
     /// connection using expensive label(s)
     #[optional]
@@ -287,29 +1391,303 @@ pub(crate) mod quic {
         reason: labels::QuicInvalidInitialPacketError, peer_ip: IpAddr,
     ) -> Counter;
 
+    /// Number of established QUIC connections by TLS server name, using
+    /// expensive label(s)
+    #[optional]
+    pub fn sni_count(sni: Arc<str>) -> Counter;
+
+    /// Number of QUIC Retry packets sent to validate a client's address
+    pub fn retry_packet_sent_count() -> Counter;
+
+    /// Number of Version Negotiation packets sent in reply to Initials with an
+    /// unsupported QUIC version
+    pub fn version_negotiation_sent_count() -> Counter;
+
+    /// Number of Version Negotiation packets received
+    pub fn version_negotiation_received_count() -> Counter;
+
+    /// Time spent validating address tokens of Initial packets
+    #[ctor = HistogramBuilder { buckets: bucket_config().token_validation_duration, }]
+    pub fn token_validation_duration() -> TimeHistogram;
+
+    /// Time from receiving a UDP packet to handing it to its connection's
+    /// IO worker
+    #[ctor = HistogramBuilder { buckets: bucket_config().packet_dispatch_latency, }]
+    pub fn packet_dispatch_latency() -> TimeHistogram;
+
+    /// Duration of UDP receive system calls
+    #[ctor = HistogramBuilder { buckets: bucket_config().udp_recv_syscall_duration, }]
+    pub fn udp_recv_syscall_duration() -> TimeHistogram;
+
+    /// Duration of UDP send system calls which sent data
+    #[ctor = HistogramBuilder { buckets: bucket_config().udp_send_syscall_duration, }]
+    pub fn udp_send_syscall_duration() -> TimeHistogram;
+
     /// Combined utilized bandwidth of all open connections (max over the past
     /// two minutes)
     pub fn utilized_bandwidth() -> Gauge;
 
     /// The highest utilized bandwidh reported during the lifetime of the
     /// connection
-    #[ctor = HistogramBuilder { buckets: &[0., 1., 2., 5., 10., 20., 50., 100., 200., 300., 500., 750., 1000., 1500., 2000., 2500., 3000., 3500., 4000., 4500., 5000., 6000., 7000., 10000.], }]
+    #[ctor = HistogramBuilder { buckets: bucket_config().max_bandwidth_mbps, }]
     pub fn max_bandwidth_mbps() -> Histogram;
 
     /// The highest momentary loss reported during the lifetime of the
     /// connection
-    #[ctor = HistogramBuilder { buckets: &[0.0, 0.1, 0.2, 0.5, 1., 2., 3., 4., 5., 10., 15., 20., 25., 50., 100.], }]
+    #[ctor = HistogramBuilder { buckets: bucket_config().max_loss_pct, }]
     pub fn max_loss_pct() -> Histogram;
 
+    /// Combined congestion window of all open connections (max over the past
+    /// round-trip of each connection)
+    pub fn cwnd_bytes() -> Gauge;
+
+    /// Smoothed RTT of QUIC connections, in microseconds
+    #[ctor = HistogramBuilder { buckets: bucket_config().rtt_micros, }]
+    pub fn rtt_micros() -> Histogram;
+
+    /// Minimum RTT of QUIC connections over their lifetime, in microseconds
+    #[ctor = HistogramBuilder { buckets: bucket_config().min_rtt_micros, }]
+    pub fn min_rtt_micros() -> Histogram;
+
+    /// Maximum RTT of QUIC connections over their lifetime, in microseconds
+    #[ctor = HistogramBuilder { buckets: bucket_config().max_rtt_micros, }]
+    pub fn max_rtt_micros() -> Histogram;
+
     /// Number of UDP packets dropped when receiving
     pub fn udp_drop_count() -> Counter;
 
+    /// Bytes queued in the send buffers of QUIC listener sockets
+    pub fn send_buffer_bytes() -> Gauge;
+
+    /// Bytes queued in the receive buffers of QUIC listener sockets
+    pub fn recv_buffer_bytes() -> Gauge;
+
+    /// Size of UDP payloads sent by QUIC connections
+    #[ctor = HistogramBuilder { buckets: bucket_config().sent_packet_size_bytes, }]
+    pub fn sent_packet_size_bytes() -> Histogram;
+
+    /// Size of UDP payloads received by QUIC connections
+    #[ctor = HistogramBuilder { buckets: bucket_config().received_packet_size_bytes, }]
+    pub fn received_packet_size_bytes() -> Histogram;
+
+    /// Number of QUIC packets coalesced into each GSO send
+    #[ctor = HistogramBuilder { buckets: bucket_config().gso_batch_size, }]
+    pub fn gso_batch_size() -> Histogram;
+
+    /// Ratio of QUIC packets to UDP datagrams in each batch of sent datagrams
+    #[ctor = HistogramBuilder { buckets: bucket_config().packet_coalescing_ratio, }]
+    pub fn packet_coalescing_ratio() -> Histogram;
+
+    /// Number of streams written per call to
+    /// `ApplicationOverQuic::process_writes`
+    #[ctor = HistogramBuilder { buckets: bucket_config().write_batch_size, }]
+    pub fn write_batch_size() -> Histogram;
+
+    /// Number of QUIC DATAGRAM frames sent
+    pub fn datagram_send_count() -> Counter;
+
+    /// Number of QUIC DATAGRAM frames received
+    pub fn datagram_recv_count() -> Counter;
+
+    /// Number of outbound QUIC DATAGRAM frames dropped before being sent
+    pub fn datagram_drop_count() -> Counter;
+
     /// Number of failed quic handshakes
     pub fn failed_handshakes(reason: labels::HandshakeError) -> Counter;
 
+    /// Number of QUIC handshakes aborted for reasons other than a TLS failure
+    pub fn handshake_abort_reason_count(
+        reason: labels::HandshakeAbortReason,
+    ) -> Counter;
+
+    /// Number of TLS alerts sent or received during QUIC handshakes
+    pub fn tls_alert_count(code: labels::TlsAlertCode) -> Counter;
+
+    /// Number of successful quic handshakes
+    pub fn handshake_success_count() -> Counter;
+
+    /// Number of client connections that sent 0-RTT packets
+    pub fn zero_rtt_attempt_count() -> Counter;
+
+    /// Number of server connections that accepted 0-RTT data
+    pub fn zero_rtt_accepted_count() -> Counter;
+
+    /// Number of connections whose 0-RTT data was rejected
+    pub fn zero_rtt_rejected_count() -> Counter;
+
+    /// Number of certificates in the peer's certificate chain, recorded once
+    /// per connection when the TLS handshake completes
+    #[ctor = HistogramBuilder { buckets: bucket_config().certificate_chain_depth, }]
+    pub fn certificate_chain_depth() -> Histogram;
+
+    /// Bytes of 0-RTT packets received by a server before the handshake
+    /// completed, for connections which accepted early data
+    #[ctor = HistogramBuilder { buckets: bucket_config().early_data_size_bytes, }]
+    pub fn early_data_size_bytes() -> Histogram;
+
+    /// Number of QUIC packets sent or received until the handshake completed
+    #[ctor = HistogramBuilder { buckets: bucket_config().handshake_packet_count, }]
+    pub fn handshake_packet_count(direction: labels::FrameDirection)
+        -> Histogram;
+
+    /// Number of active source connection IDs of a connection, sampled every
+    /// second
+    #[ctor = HistogramBuilder { buckets: bucket_config().active_connection_id_count, }]
+    pub fn active_connection_id_count() -> Histogram;
+
+    /// Number of client handshakes that attempted to resume a TLS session
+    pub fn tls_session_resumption_attempted() -> Counter;
+
+    /// Number of client handshakes that successfully resumed a TLS session
+    pub fn tls_session_resumption_succeeded() -> Counter;
+
+    /// Packet loss percentage of a connection, sampled at a fixed interval over
+    /// the connection's lifetime
+    #[ctor = HistogramBuilder { buckets: bucket_config().packet_loss_rate_pct, }]
+    pub fn packet_loss_rate_pct() -> Histogram;
+
+    /// Time spent waiting for the UDP socket to become writable again after the
+    /// kernel send buffer filled up
+    #[ctor = HistogramBuilder { buckets: bucket_config().write_blocked_duration, }]
+    pub fn write_blocked_duration() -> TimeHistogram;
+
+    /// Time an HTTP/3 stream spent waiting for more body data from the peer
+    /// after all received data was handed to the application
+    #[ctor = HistogramBuilder { buckets: bucket_config().read_blocked_duration, }]
+    pub fn read_blocked_duration() -> TimeHistogram;
+
+    /// Number of connection attempts that failed before a handshake was started
+    pub fn connection_error_before_handshake_count(
+        reason: labels::PreHandshakeError,
+    ) -> Counter;
+
+    /// Number of stream data bytes sent again after the packet carrying them
+    /// was declared lost
+    pub fn bytes_retransmitted() -> Counter;
+
+    /// Bandwidth estimate of a connection in Mbps, sampled every second
+    #[ctor = HistogramBuilder { buckets: bucket_config().bandwidth_estimate_mbps, }]
+    pub fn bandwidth_estimate_mbps() -> Histogram;
+
+    /// Time between the completion of the handshake and the opening of the
+    /// first HTTP/3 request stream on a connection
+    #[ctor = HistogramBuilder { buckets: bucket_config().connection_age_at_first_stream, }]
+    pub fn connection_age_at_first_stream() -> TimeHistogram;
+
+    /// Number of HTTP/3 request streams a connection carried before it was
+    /// closed
+    #[ctor = HistogramBuilder { buckets: bucket_config().streams_per_connection_histogram, }]
+    pub fn streams_per_connection_histogram() -> Histogram;
+
+    /// Size of HTTP/3 request bodies
+    #[ctor = HistogramBuilder { buckets: bucket_config().h3_request_body_size_bytes, }]
+    pub fn h3_request_body_size_bytes() -> Histogram;
+
+    /// Size of HTTP/3 response bodies
+    #[ctor = HistogramBuilder { buckets: bucket_config().h3_response_body_size_bytes, }]
+    pub fn h3_response_body_size_bytes() -> Histogram;
+
+    /// Time between receiving an HTTP/3 request and sending the first frame of
+    /// its response
+    #[ctor = HistogramBuilder { buckets: bucket_config().h3_time_to_first_byte, }]
+    pub fn h3_time_to_first_byte() -> TimeHistogram;
+
+    /// Time between receiving the last byte of an HTTP/3 request and the peer
+    /// acknowledging the last byte of its response
+    #[ctor = HistogramBuilder { buckets: bucket_config().h3_total_request_duration, }]
+    pub fn h3_total_request_duration() -> TimeHistogram;
+
+    /// Number of stream bytes written by the application which have not been
+    /// accepted by the QUIC connection for transmission yet, sampled once per
+    /// second
+    pub fn pending_send_data_bytes() -> Gauge;
+
+    /// Maximum ACK delay advertised by the peer in its transport parameters
+    #[ctor = HistogramBuilder { buckets: bucket_config().max_ack_delay_micros, }]
+    pub fn max_ack_delay_micros() -> Histogram;
+
+    /// Number of connection attempts which reached each stage, from being
+    /// started to being closed
+    pub fn connection_attempt_count(
+        outcome: labels::ConnectionAttemptOutcome,
+    ) -> Counter;
+
+    /// Number of received packets dropped because the connection's incoming
+    /// packet queue was full
+    pub fn receive_queue_overflow_count() -> Counter;
+
+    /// Number of connections created with an IPv4 peer
+    pub fn ipv4_connection_count() -> Counter;
+
+    /// Number of connections created with an IPv6 peer
+    pub fn ipv6_connection_count() -> Counter;
+
+    /// Number of connections migrated by the peer to a validated path
+    pub fn connection_migration_success_count() -> Counter;
+
+    /// Number of failed connection migrations
+    pub fn connection_migration_failure_count(
+        reason: labels::MigrationFailureReason,
+    ) -> Counter;
+
+    /// Time from sending a PATH_CHALLENGE on a new path to receiving the
+    /// matching PATH_RESPONSE
+    #[ctor = HistogramBuilder { buckets: bucket_config().path_validation_duration, }]
+    pub fn path_validation_duration() -> TimeHistogram;
+
+    /// Number of QUIC frames, by frame type
+    pub fn frame_count(
+        frame_type: labels::QuicFrameType, direction: labels::FrameDirection,
+    ) -> Counter;
+
+    /// Number of `CONNECTION_CLOSE` frames, by whether they carry a transport
+    /// or application error
+    pub fn connection_close_frame_type_count(
+        frame_type: labels::CloseFrameType, direction: labels::FrameDirection,
+    ) -> Counter;
+
+    /// Number of HTTP/3 requests received
+    pub fn h3_request_count() -> Counter;
+
+    /// Number of HTTP/3 requests sent on a QUIC connection that already carried
+    /// a request
+    pub fn connection_reuse_count() -> Counter;
+
+    /// Number of HTTP/3 responses sent, by status class
+    pub fn h3_response_status_count(
+        status_class: labels::HttpStatusClass,
+    ) -> Counter;
+
+    /// Number of HTTP/3 GOAWAY frames sent
+    pub fn h3_goaway_sent_count() -> Counter;
+
+    /// Number of HTTP/3 GOAWAY frames received
+    pub fn h3_goaway_received_count() -> Counter;
+
+    /// Number of HTTP/3 SETTINGS frames sent
+    pub fn h3_settings_sent_count() -> Counter;
+
+    /// Number of HTTP/3 SETTINGS frames received
+    pub fn h3_settings_received_count() -> Counter;
+
+    /// Number of HTTP/3 unidirectional streams opened by either endpoint, by
+    /// stream type
+    pub fn h3_unidirectional_stream_opened(
+        stream_type: labels::H3StreamType,
+    ) -> Counter;
+
+    /// Number of accepted connections by negotiated QUIC version
+    pub fn negotiated_version_count(version: labels::QuicVersion) -> Counter;
+
     /// Number of HTTP/3 connection closures generated locally
     pub fn local_h3_conn_close_error_count(reason: labels::H3Error) -> Counter;
 
+    /// Number of HTTP/3 connection closures generated locally due to a QPACK
+    /// error
+    pub fn local_qpack_conn_close_error_count(
+        reason: labels::QpackError,
+    ) -> Counter;
+
     /// Number of QUIC connection closures generated locally
     pub fn local_quic_conn_close_error_count(
         reason: labels::QuicError,
@@ -318,36 +1696,123 @@ pub(crate) mod quic {
     /// Number of HTTP/3 connection closures generated by peer
     pub fn peer_h3_conn_close_error_count(reason: labels::H3Error) -> Counter;
 
+    /// Number of HTTP/3 connection closures generated by peer due to a QPACK
+    /// error
+    pub fn peer_qpack_conn_close_error_count(
+        reason: labels::QpackError,
+    ) -> Counter;
+
     /// Number of QUIC connection closures generated by peer
     pub fn peer_quic_conn_close_error_count(reason: labels::QuicError)
         -> Counter;
+
+    /// Number of bytes sent on a QUIC stream over its lifetime
+    #[ctor = HistogramBuilder { buckets: bucket_config().stream_bytes_sent, }]
+    pub fn stream_bytes_sent() -> Histogram;
+
+    /// Number of bytes received on a QUIC stream over its lifetime
+    #[ctor = HistogramBuilder { buckets: bucket_config().stream_bytes_received, }]
+    pub fn stream_bytes_received() -> Histogram;
+
+    /// Number of QUIC streams opened
+    pub fn streams_opened(
+        initiator: labels::StreamInitiator, direction: labels::StreamDirection,
+    ) -> Counter;
+
+    /// Number of QUIC streams closed
+    pub fn streams_closed(
+        initiator: labels::StreamInitiator, direction: labels::StreamDirection,
+    ) -> Counter;
+
+    /// Number of QUIC streams currently open across all connections
+    pub fn open_streams() -> Gauge;
+
+    /// Number of QUIC streams reset by the peer
+    pub fn stream_reset_count(reason: labels::StreamResetReason) -> Counter;
+
+    /// Number of QUIC streams reset locally
+    pub fn peer_stream_reset_count(reason: labels::StreamResetReason) -> Counter;
+
+    /// Time QUIC streams spent blocked on flow control
+    #[ctor = HistogramBuilder { buckets: bucket_config().stream_flow_control_blocked_duration, }]
+    pub fn stream_flow_control_blocked_duration(
+        direction: labels::FlowControlDirection,
+    ) -> TimeHistogram;
+
+    /// Ratio of QPACK-encoded to uncompressed size of sent HTTP/3 headers
+    #[ctor = HistogramBuilder { buckets: bucket_config().h3_header_compression_ratio, }]
+    pub fn h3_header_compression_ratio() -> Histogram;
+
+    /// SETTINGS_MAX_FIELD_SECTION_SIZE advertised by peers, in bytes
+    #[ctor = HistogramBuilder { buckets: bucket_config().h3_settings_max_field_section_size_histogram, }]
+    pub fn h3_settings_max_field_section_size_histogram() -> Histogram;
 }
 
 #[metrics]
 mod tokio {
     /// Histogram of task schedule delays
-    #[ctor = HistogramBuilder { buckets: &[0.0, 1E-4, 2E-4, 3E-4, 4E-4, 5E-4, 6E-4, 7E-4, 8E-4, 9E-4, 1E-3, 1E-2, 2E-2, 4E-2, 8E-2, 1E-1, 1.0], }]
+    #[ctor = HistogramBuilder { buckets: bucket_config().tokio_runtime_task_schedule_delay_histogram, }]
     pub fn runtime_task_schedule_delay_histogram(
         task: &Arc<str>,
     ) -> TimeHistogram;
 
     /// Histogram of task poll durations
-    #[ctor = HistogramBuilder { buckets: &[0.0, 1E-4, 2E-4, 3E-4, 4E-4, 5E-4, 6E-4, 7E-4, 8E-4, 9E-4, 1E-3, 1E-2, 2E-2, 4E-2, 8E-2, 1E-1, 1.0], }]
+    #[ctor = HistogramBuilder { buckets: bucket_config().tokio_runtime_task_poll_duration_histogram, }]
     pub fn runtime_task_poll_duration_histogram(task: &Arc<str>)
         -> TimeHistogram;
 
     /// Helps us get a rough idea of if our waker is causing issues.
     pub fn runtime_task_total_poll_time_micros(task: &Arc<str>) -> Counter;
+
+    /// Number of times a task was woken
+    pub fn runtime_task_wakeup_count(task: &Arc<str>) -> Counter;
+
+    /// CPU time spent by each runtime thread polling instrumented tasks
+    pub fn worker_thread_cpu_time_micros(worker: usize) -> Counter;
 }
 
-pub(crate) fn quic_expensive_metrics_ip_reduce(ip: IpAddr) -> Option<IpAddr> {
-    const QUIC_INITIAL_METRICS_V4_PREFIX: u8 = 20;
-    const QUIC_INITIAL_METRICS_V6_PREFIX: u8 = 32;
+/// Prefix lengths used to reduce peer IPs before they are used as labels in
+/// expensive metrics.
+///
+/// Shorter prefixes group more peers into a single label value, which lowers
+/// the cardinality of the metric at the cost of precision. Longer prefixes
+/// make it easier to attribute traffic to individual networks, but every
+/// additional prefix bit can double the number of time series emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpReduceConfig {
+    /// Prefix length applied to IPv4 addresses. Defaults to `/20`.
+    pub v4_prefix: u8,
+    /// Prefix length applied to IPv6 addresses. Defaults to `/32`.
+    pub v6_prefix: u8,
+}
+
+impl Default for IpReduceConfig {
+    fn default() -> Self {
+        Self {
+            v4_prefix: 20,
+            v6_prefix: 32,
+        }
+    }
+}
 
+/// Reduces `ip` to the network address of its enclosing prefix, as defined
+/// by `config`.
+///
+/// The result is meant to be used as a label value, so the prefix lengths
+/// directly control the cardinality of the resulting metric. For example,
+/// `/24` for IPv4 matches the granularity of many existing dashboards but
+/// yields 16 times as many series as `/20`. For IPv6, `/32` roughly maps to
+/// a single provider allocation, which may still be too fine-grained for
+/// high-traffic deployments. `/16` or shorter prefixes can be used there.
+///
+/// Returns `None` if a prefix length is out of range for the address family.
+pub fn quic_expensive_metrics_ip_reduce_with_config(
+    ip: IpAddr, config: &IpReduceConfig,
+) -> Option<IpAddr> {
     let prefix = if ip.is_ipv4() {
-        QUIC_INITIAL_METRICS_V4_PREFIX
+        config.v4_prefix
     } else {
-        QUIC_INITIAL_METRICS_V6_PREFIX
+        config.v6_prefix
     };
 
     if let Ok(ip_net) = ipnetwork::IpNetwork::new(ip, prefix) {
@@ -356,3 +1821,56 @@ pub(crate) fn quic_expensive_metrics_ip_reduce(ip: IpAddr) -> Option<IpAddr> {
         None
     }
 }
+
+/// Reduces `ip` using the default [`IpReduceConfig`].
+pub(crate) fn quic_expensive_metrics_ip_reduce(ip: IpAddr) -> Option<IpAddr> {
+    quic_expensive_metrics_ip_reduce_with_config(ip, &IpReduceConfig::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reduce(ip: &str, v4_prefix: u8, v6_prefix: u8) -> Option<IpAddr> {
+        let config = IpReduceConfig {
+            v4_prefix,
+            v6_prefix,
+        };
+
+        quic_expensive_metrics_ip_reduce_with_config(ip.parse().unwrap(), &config)
+    }
+
+    #[test]
+    fn ip_reduce_v4() {
+        let ip = "192.168.171.205";
+
+        assert_eq!(reduce(ip, 20, 32), Some("192.168.160.0".parse().unwrap()));
+        assert_eq!(reduce(ip, 24, 32), Some("192.168.171.0".parse().unwrap()));
+        assert_eq!(reduce(ip, 0, 32), Some("0.0.0.0".parse().unwrap()));
+        assert_eq!(reduce(ip, 32, 32), Some(ip.parse().unwrap()));
+        assert_eq!(reduce(ip, 33, 32), None);
+    }
+
+    #[test]
+    fn ip_reduce_v6() {
+        let ip = "2001:db8:abcd:1234::1";
+
+        assert_eq!(reduce(ip, 20, 32), Some("2001:db8::".parse().unwrap()));
+        assert_eq!(reduce(ip, 20, 48), Some("2001:db8:abcd::".parse().unwrap()));
+        assert_eq!(reduce(ip, 20, 0), Some("::".parse().unwrap()));
+        assert_eq!(reduce(ip, 20, 128), Some(ip.parse().unwrap()));
+        assert_eq!(reduce(ip, 20, 129), None);
+    }
+
+    #[test]
+    fn ip_reduce_default_config() {
+        assert_eq!(
+            quic_expensive_metrics_ip_reduce("10.1.31.7".parse().unwrap()),
+            Some("10.1.16.0".parse().unwrap())
+        );
+        assert_eq!(
+            quic_expensive_metrics_ip_reduce("2001:db8:1::1".parse().unwrap()),
+            Some("2001:db8::".parse().unwrap())
+        );
+    }
+}
@@ -35,17 +35,26 @@
 //! than we would like.
 
 use crate::metrics::Metrics;
+use foundations::telemetry::metrics::Counter;
 use foundations::telemetry::TelemetryContext;
 use pin_project::pin_project;
+#[cfg(all(target_os = "linux", feature = "tokio-task-cpu-metrics"))]
+use std::cell::Cell;
 use std::future::Future;
 use std::pin::pin;
 use std::pin::Pin;
+#[cfg(all(target_os = "linux", feature = "tokio-task-cpu-metrics"))]
+use std::sync::atomic::AtomicUsize;
+#[cfg(all(target_os = "linux", feature = "tokio-task-cpu-metrics"))]
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::task::Context;
 use std::task::Poll;
 use std::task::Wake;
 use std::task::Waker;
+#[cfg(all(target_os = "linux", feature = "tokio-task-cpu-metrics"))]
+use std::time::Duration;
 use std::time::Instant;
 use task_killswitch::spawn_with_killswitch as killswitch_spawn;
 use tokio::task::JoinHandle;
@@ -60,6 +69,7 @@ struct Instrumented<F, M> {
     future: F,
     name: Arc<str>,
     timer: Arc<Mutex<Option<Instant>>>,
+    wakeup_count: Counter,
     metrics: M,
 }
 
@@ -69,6 +79,7 @@ struct Instrumented<F, M> {
 /// is concerned.
 struct InstrumentedWaker {
     timer: Arc<Mutex<Option<Instant>>>,
+    wakeup_count: Counter,
     waker: Waker,
 }
 
@@ -78,6 +89,8 @@ impl Wake for InstrumentedWaker {
     }
 
     fn wake_by_ref(self: &Arc<Self>) {
+        self.wakeup_count.inc();
+
         // let's scope the guard's lifespan in case the inner waker is slow
         // this is still highly unlikely to be contended ever
         {
@@ -98,12 +111,14 @@ where
 {
     fn new(name: &str, metrics: M, future: F) -> Self {
         let name = Arc::from(name);
+        let wakeup_count = metrics.tokio_runtime_task_wakeup_count(&name);
 
         Self {
             future,
             name,
             metrics,
             timer: Arc::new(Mutex::new(Some(Instant::now()))),
+            wakeup_count,
         }
     }
 }
@@ -113,6 +128,8 @@ impl<F: Future, M: Metrics> Future for Instrumented<F, M> {
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let total_timer = Instant::now();
+        #[cfg(all(target_os = "linux", feature = "tokio-task-cpu-metrics"))]
+        let cpu_timer = thread_cpu_time();
 
         // if we were to hold the lock over the poll boundary, self-wakes would
         // deadlock us, so we won't do that.
@@ -120,35 +137,24 @@ impl<F: Future, M: Metrics> Future for Instrumented<F, M> {
         // this is unlikely to be contended much otherwise.
         let maybe_schedule_timer = self.timer.lock().unwrap().take();
 
-        // for various reasons related to how rust does lifetime things, we will
-        // not acquire the lock in the if statement
-        if let Some(schedule_timer) = maybe_schedule_timer {
-            let elapsed = schedule_timer.elapsed();
-
-            self.metrics
-                .tokio_runtime_task_schedule_delay_histogram(&self.name)
-                .observe(elapsed.as_nanos() as u64);
-        }
-
         let projected = self.project();
 
         let waker = Waker::from(Arc::new(InstrumentedWaker {
             timer: Arc::clone(projected.timer),
+            wakeup_count: projected.wakeup_count.clone(),
             waker: cx.waker().clone(),
         }));
 
         let mut new_cx = Context::from_waker(&waker);
 
-        let timer = Instant::now();
-
-        let output = projected.future.poll(&mut new_cx);
-
-        let elapsed = timer.elapsed();
-
-        projected
-            .metrics
-            .tokio_runtime_task_poll_duration_histogram(projected.name)
-            .observe(elapsed.as_nanos() as u64);
+        let output = {
+            let _guard = TaskMetricsGuard::new(
+                projected.name,
+                projected.metrics,
+                maybe_schedule_timer,
+            );
+            projected.future.poll(&mut new_cx)
+        };
 
         let total_elapsed = total_timer.elapsed();
 
@@ -157,10 +163,127 @@ impl<F: Future, M: Metrics> Future for Instrumented<F, M> {
             .tokio_runtime_task_total_poll_time_micros(projected.name)
             .inc_by(total_elapsed.as_micros() as u64);
 
+        #[cfg(all(target_os = "linux", feature = "tokio-task-cpu-metrics"))]
+        if let (Some(start), Some(end)) = (cpu_timer, thread_cpu_time()) {
+            WORKER_CPU_TIME.with(|cpu_time| {
+                let micros = cpu_time.add(end.saturating_sub(start));
+                if micros > 0 {
+                    projected
+                        .metrics
+                        .worker_thread_cpu_time_micros(cpu_time.worker)
+                        .inc_by(micros);
+                }
+            });
+        }
+
         output
     }
 }
 
+/// Index of the next thread to poll an instrumented task.
+#[cfg(all(target_os = "linux", feature = "tokio-task-cpu-metrics"))]
+static NEXT_WORKER: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(all(target_os = "linux", feature = "tokio-task-cpu-metrics"))]
+thread_local! {
+    static WORKER_CPU_TIME: WorkerCpuTime = WorkerCpuTime {
+        worker: NEXT_WORKER.fetch_add(1, Ordering::Relaxed),
+        nanos: Cell::new(0),
+    };
+}
+
+/// CPU time accounting for the calling thread.
+///
+/// Polls are usually much shorter than a microsecond, so the CPU time is
+/// accumulated in nanoseconds and only whole microseconds are reported.
+#[cfg(all(target_os = "linux", feature = "tokio-task-cpu-metrics"))]
+struct WorkerCpuTime {
+    worker: usize,
+    nanos: Cell<u128>,
+}
+
+#[cfg(all(target_os = "linux", feature = "tokio-task-cpu-metrics"))]
+impl WorkerCpuTime {
+    /// Adds `elapsed` and returns the whole microseconds to report.
+    fn add(&self, elapsed: Duration) -> u64 {
+        let nanos = self.nanos.get() + elapsed.as_nanos();
+        self.nanos.set(nanos % 1000);
+        (nanos / 1000) as u64
+    }
+}
+
+/// Returns the CPU time consumed by the calling thread so far.
+#[cfg(all(target_os = "linux", feature = "tokio-task-cpu-metrics"))]
+fn thread_cpu_time() -> Option<Duration> {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+
+    // SAFETY: `ts` is a valid pointer to a `timespec`
+    let res =
+        unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut ts) };
+    if res != 0 {
+        return None;
+    }
+
+    Some(Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+}
+
+/// Records the metrics of a single poll of a task.
+///
+/// Creating the guard records the schedule delay, i.e. the time since the task
+/// was woken, if given. Dropping the guard records the time since it was
+/// created as the poll duration.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use std::time::Instant;
+/// use tokio_quiche::metrics::tokio_task::TaskMetricsGuard;
+/// use tokio_quiche::metrics::DefaultMetrics;
+///
+/// let name = Arc::from("my_task");
+/// let metrics = DefaultMetrics;
+/// let woken_at = Instant::now();
+///
+/// let _guard = TaskMetricsGuard::new(&name, &metrics, Some(woken_at));
+/// // ... poll the task ...
+/// ```
+pub struct TaskMetricsGuard<'a, M: Metrics> {
+    name: &'a Arc<str>,
+    metrics: &'a M,
+    poll_start: Instant,
+}
+
+impl<'a, M: Metrics> TaskMetricsGuard<'a, M> {
+    /// Starts timing a poll of the task `name`. `woken_at` is the time the task
+    /// was scheduled, or [`None`] if the schedule delay is unknown.
+    pub fn new(
+        name: &'a Arc<str>, metrics: &'a M, woken_at: Option<Instant>,
+    ) -> Self {
+        if let Some(woken_at) = woken_at {
+            metrics
+                .tokio_runtime_task_schedule_delay_histogram(name)
+                .observe(woken_at.elapsed().as_nanos() as u64);
+        }
+
+        Self {
+            name,
+            metrics,
+            poll_start: Instant::now(),
+        }
+    }
+}
+
+impl<M: Metrics> Drop for TaskMetricsGuard<'_, M> {
+    fn drop(&mut self) {
+        self.metrics
+            .tokio_runtime_task_poll_duration_histogram(self.name)
+            .observe(self.poll_start.elapsed().as_nanos() as u64);
+    }
+}
+
 /// Spawn a potentially instrumented task.
 ///
 /// Depending on whether the `tokio-task-metrics` feature is enabled, this may
@@ -61,6 +61,7 @@ use super::io::connection_stage::Close;
 use super::io::connection_stage::ConnectionStageContext;
 use super::io::connection_stage::Handshake;
 use super::io::connection_stage::RunningApplication;
+use super::io::rtt_reporter::RttReporter;
 use super::io::worker::Closing;
 use super::io::worker::IoWorkerParams;
 use super::io::worker::Running;
@@ -68,6 +69,7 @@ use super::io::worker::RunningOrClosing;
 use super::io::worker::WriteState;
 use super::QuicheConnection;
 use crate::buf_factory::PooledBuf;
+use crate::metrics::labels;
 use crate::metrics::Metrics;
 use crate::quic::io::worker::IoWorker;
 use crate::quic::io::worker::WriterConfig;
@@ -82,14 +84,48 @@ pub struct QuicConnectionStats {
     pub stats: quiche::Stats,
     /// Specific statistics about the connection's active path.
     pub path_stats: Option<quiche::PathStats>,
+    /// Number of streams the [`ApplicationOverQuic`] reported as open, if it
+    /// keeps track of them.
+    pub streams_open: Option<u64>,
 }
 pub(crate) type QuicConnectionStatsShared = Arc<Mutex<QuicConnectionStats>>;
 
 impl QuicConnectionStats {
-    pub(crate) fn from_conn(qconn: &QuicheConnection) -> Self {
+    pub(crate) fn from_conn(
+        qconn: &QuicheConnection, streams_open: Option<u64>,
+    ) -> Self {
         Self {
             stats: qconn.stats(),
             path_stats: qconn.path_stats().next(),
+            streams_open,
+        }
+    }
+
+    /// Summarizes the statistics into a [`MetricsSnapshot`].
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let path = self.path_stats.as_ref();
+        let loss_pct = if self.stats.sent == 0 {
+            0.0
+        } else {
+            self.stats.lost as f64 * 100.0 / self.stats.sent as f64
+        };
+
+        MetricsSnapshot {
+            rtt: path.map(|p| p.rtt),
+            min_rtt: path.and_then(|p| p.min_rtt),
+            cwnd: path.map(|p| p.cwnd),
+            pmtu: path.map(|p| p.pmtu),
+            delivery_rate: path.map(|p| p.delivery_rate),
+            loss_pct,
+            packets_sent: self.stats.sent,
+            packets_received: self.stats.recv,
+            packets_lost: self.stats.lost,
+            bytes_sent: self.stats.sent_bytes,
+            bytes_received: self.stats.recv_bytes,
+            bytes_lost: self.stats.lost_bytes,
+            streams_reset_local: self.stats.reset_stream_count_local,
+            streams_reset_remote: self.stats.reset_stream_count_remote,
+            streams_open: self.streams_open,
         }
     }
 }
@@ -124,6 +160,45 @@ impl AsSocketStats for QuicConnectionStats {
     }
 }
 
+/// A point-in-time summary of a connection's state, intended for one-shot
+/// diagnostics such as structured logging at lifecycle events.
+///
+/// Path-specific fields are [`None`] if no path statistics were recorded.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    /// Smoothed RTT of the active path.
+    pub rtt: Option<Duration>,
+    /// Minimum RTT of the active path, if an RTT sample was taken.
+    pub min_rtt: Option<Duration>,
+    /// Congestion window of the active path, in bytes.
+    pub cwnd: Option<usize>,
+    /// Path MTU of the active path, in bytes.
+    pub pmtu: Option<usize>,
+    /// Estimated delivery rate of the active path, in bytes per second.
+    pub delivery_rate: Option<u64>,
+    /// Percentage of sent packets that were declared lost.
+    pub loss_pct: f64,
+    /// Number of QUIC packets sent.
+    pub packets_sent: usize,
+    /// Number of QUIC packets received.
+    pub packets_received: usize,
+    /// Number of QUIC packets declared lost.
+    pub packets_lost: usize,
+    /// Number of bytes sent.
+    pub bytes_sent: u64,
+    /// Number of bytes received.
+    pub bytes_received: u64,
+    /// Number of bytes declared lost.
+    pub bytes_lost: u64,
+    /// Number of streams reset by the local endpoint.
+    pub streams_reset_local: u64,
+    /// Number of streams reset by the peer.
+    pub streams_reset_remote: u64,
+    /// Number of streams currently open, if the [`ApplicationOverQuic`]
+    /// reports it.
+    pub streams_open: Option<u64>,
+}
+
 /// A received network packet with additional metadata.
 #[derive(Debug)]
 pub struct Incoming {
@@ -189,6 +264,7 @@ where
 
         let stats = Arc::new(Mutex::new(QuicConnectionStats::from_conn(
             &params.quiche_conn,
+            None,
         )));
 
         Self {
@@ -232,8 +308,8 @@ where
     ///
     /// # Note
     /// Initially, these stats represent the state when the [quiche::Connection]
-    /// was created. They are updated when the connection is closed, so this
-    /// getter exists primarily to grab a handle early on.
+    /// was created. The connection's worker updates them at most every 100ms
+    /// while the connection is open, and a final time when it is closed.
     #[inline]
     pub fn stats(&self) -> &QuicConnectionStatsShared {
         &self.stats
@@ -246,12 +322,35 @@ where
     /// `handshake` for details on the return values.
     #[allow(clippy::type_complexity)]
     pub fn handshake_fut<A: ApplicationOverQuic>(
-        self, app: A,
+        mut self, app: A,
     ) -> (
         QuicConnection,
         BoxFuture<'static, io::Result<Running<Arc<Tx>, M, A>>>,
     ) {
         self.params.metrics.connections_in_memory().inc();
+        self.params
+            .metrics
+            .connection_attempt_count(labels::ConnectionAttemptOutcome::Started)
+            .inc();
+
+        // Dual-stack sockets report IPv4 peers as IPv4-mapped IPv6 addresses
+        if self.params.peer_addr.ip().to_canonical().is_ipv4() {
+            self.params.metrics.ipv4_connection_count().inc();
+        } else {
+            self.params.metrics.ipv6_connection_count().inc();
+        }
+
+        self.params
+            .metrics
+            .memory_usage_bytes(labels::MemoryCategory::ConnectionState)
+            .inc_by(size_of::<QuicheConnection>() as u64);
+
+        // A session set on a client connection is offered to the server
+        if !self.params.quiche_conn.is_server() &&
+            self.ssl_mut().session().is_some()
+        {
+            self.params.metrics.tls_session_resumption_attempted().inc();
+        }
 
         let conn = QuicConnection {
             local_addr: self.params.local_addr,
@@ -268,6 +367,7 @@ where
         };
         let conn_stage = Handshake {
             handshake_info: self.params.handshake_info,
+            saw_early_data: false,
         };
         let params = IoWorkerParams {
             socket: MaybeConnectedSocket::new(self.params.socket),
@@ -278,6 +378,12 @@ where
             conn_map_cmd_tx: self.params.conn_map_cmd_tx,
             #[cfg(feature = "perf-quic-listener-metrics")]
             init_rx_time: self.params.init_rx_time,
+            start_time: conn_stage.handshake_info.start_time(),
+            rtt_reporter: RttReporter::new(
+                self.params.metrics.rtt_micros(),
+                self.params.metrics.min_rtt_micros(),
+                self.params.metrics.max_rtt_micros(),
+            ),
             metrics: self.params.metrics.clone(),
         };
 
@@ -467,13 +573,24 @@ impl QuicConnection {
     ///
     /// # Note
     /// Initially, these stats represent the state when the [quiche::Connection]
-    /// was created. They are updated when the connection is closed, so this
-    /// getter exists primarily to grab a handle early on.
+    /// was created. The connection's worker updates them at most every 100ms
+    /// while the connection is open, and a final time when it is closed.
     #[inline]
     pub fn stats(&self) -> &QuicConnectionStatsShared {
         &self.stats
     }
 
+    /// A [`MetricsSnapshot`] of this connection's current [`stats()`].
+    ///
+    /// # Note
+    /// Like [`stats()`], the snapshot may lag the connection's actual state by
+    /// up to 100ms while the connection is open.
+    ///
+    /// [`stats()`]: Self::stats
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.stats.lock().unwrap().snapshot()
+    }
+
     /// The QUIC source connection ID used by this connection.
     #[inline]
     pub fn scid(&self) -> &ConnectionId<'static> {
@@ -484,9 +601,9 @@ impl QuicConnection {
 impl AsSocketStats for QuicConnection {
     #[inline]
     fn as_socket_stats(&self) -> SocketStats {
-        // It is important to note that those stats are only updated when
-        // the connection stops, which is fine, since this is only used to
-        // log after the connection is finished.
+        // It is important to note that those stats are only updated
+        // periodically, which is fine, since this is only used to log after
+        // the connection is finished.
         self.stats.lock().unwrap().as_socket_stats()
     }
 
@@ -503,9 +620,9 @@ where
 {
     #[inline]
     fn as_socket_stats(&self) -> SocketStats {
-        // It is important to note that those stats are only updated when
-        // the connection stops, which is fine, since this is only used to
-        // log after the connection is finished.
+        // It is important to note that those stats are only updated
+        // periodically, which is fine, since this is only used to log after
+        // the connection is finished.
         self.stats.lock().unwrap().as_socket_stats()
     }
 
@@ -674,6 +791,22 @@ pub trait ApplicationOverQuic: Send + 'static {
     /// and transitions to the connection closing stage.
     fn process_writes(&mut self, qconn: &mut QuicheConnection) -> QuicResult<()>;
 
+    /// Reports application-level metrics gathered since the last call, such
+    /// as per-stream statistics.
+    ///
+    /// This method is called once per worker loop iteration, after all
+    /// pending packets were flushed to the network (provided `should_act()`
+    /// returns true).
+    fn report_metrics<M: Metrics>(&mut self, metrics: &M) {}
+
+    /// Number of streams the application currently has open, if it keeps
+    /// track of them.
+    ///
+    /// This is reported in the connection's [`MetricsSnapshot`].
+    fn streams_open(&self) -> Option<u64> {
+        None
+    }
+
     /// Callback to inspect the result of the worker task, before a final packet
     /// with a `CONNECTION_CLOSE` frame is flushed to the network.
     ///
@@ -731,7 +864,7 @@ impl QuicCommand {
                 (f)(qconn);
             },
             Self::Stats(callback) => {
-                let stats_pair = QuicConnectionStats::from_conn(qconn);
+                let stats_pair = QuicConnectionStats::from_conn(qconn, None);
                 (callback)(stats_pair.as_socket_stats());
             },
         }
@@ -0,0 +1,94 @@
+// Copyright (C) 2025, Cloudflare, Inc.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+// IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+// THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+// PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use foundations::telemetry::metrics::Histogram;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::quic::QuicheConnection;
+
+/// Interval between two samples of a connection's active connection IDs.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// [`CidReporter`] periodically samples the number of active source
+/// connection IDs of a connection into a histogram.
+pub(super) struct CidReporter {
+    /// Time of the last sample
+    last_sample: Option<Instant>,
+
+    active_cids_histogram: Histogram,
+}
+
+impl CidReporter {
+    pub(super) fn new(active_cids_histogram: Histogram) -> Self {
+        CidReporter {
+            last_sample: None,
+            active_cids_histogram,
+        }
+    }
+
+    #[inline]
+    pub(super) fn update(&mut self, quiche: &QuicheConnection, now: Instant) {
+        if self.should_sample(now) {
+            self.active_cids_histogram
+                .observe(quiche.active_scids() as f64);
+        }
+    }
+
+    /// Returns whether a sample is due at `now`, and if so starts a new
+    /// interval.
+    fn should_sample(&mut self, now: Instant) -> bool {
+        if self
+            .last_sample
+            .is_some_and(|last| now.duration_since(last) < SAMPLE_INTERVAL)
+        {
+            return false;
+        }
+
+        self.last_sample = Some(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_once_per_interval() {
+        let mut reporter = CidReporter::new(Histogram::new(std::iter::empty()));
+        let start = Instant::now();
+
+        // The first update is always sampled
+        assert!(reporter.should_sample(start));
+        assert!(!reporter.should_sample(start + Duration::from_millis(999)));
+        assert!(reporter.should_sample(start + SAMPLE_INTERVAL));
+
+        // The next interval starts at the last sample
+        assert!(!reporter.should_sample(start + Duration::from_millis(1500)));
+        assert!(reporter.should_sample(start + SAMPLE_INTERVAL * 2));
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright (C) 2025, Cloudflare, Inc.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+// IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+// THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+// PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+use foundations::telemetry::metrics::Gauge;
+
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::quic::QuicheConnection;
+
+/// [`CongestionReporter`] contributes the congestion window of a connection to
+/// a gauge that is shared by all open connections.
+///
+/// The contribution is the maximum congestion window observed during the last
+/// reporting interval, which lasts one RTT.
+pub(super) struct CongestionReporter {
+    /// Time of last update
+    last_update: Instant,
+    /// Period between update (set using rtt)
+    update_period: Duration,
+    /// Max congestion window observed since the last update
+    max_cwnd: u64,
+    /// Congestion window currently added to the gauge
+    reported_cwnd: u64,
+
+    cwnd_gauge: Gauge,
+}
+
+impl CongestionReporter {
+    pub(super) fn new(cwnd_gauge: Gauge) -> Self {
+        CongestionReporter {
+            last_update: Instant::now(),
+            update_period: Duration::from_millis(50),
+
+            max_cwnd: 0,
+            reported_cwnd: 0,
+
+            cwnd_gauge,
+        }
+    }
+
+    #[inline]
+    pub(super) fn update(&mut self, quiche: &QuicheConnection, now: Instant) {
+        let Some(path) = quiche.path_stats().find(|s| s.active) else {
+            return;
+        };
+
+        self.record(path.cwnd as u64, path.rtt, now);
+    }
+
+    /// Records a congestion window sample of the active path, and reports
+    /// the maximum of the interval once it has lasted `rtt`.
+    fn record(&mut self, cwnd: u64, rtt: Duration, now: Instant) {
+        self.max_cwnd = self.max_cwnd.max(cwnd);
+
+        if now.duration_since(self.last_update) < self.update_period {
+            return;
+        }
+
+        self.cwnd_gauge.dec_by(self.reported_cwnd);
+        self.reported_cwnd = std::mem::take(&mut self.max_cwnd);
+        self.cwnd_gauge.inc_by(self.reported_cwnd);
+
+        self.last_update = now;
+        self.update_period = rtt;
+    }
+}
+
+impl Drop for CongestionReporter {
+    fn drop(&mut self) {
+        self.cwnd_gauge.dec_by(self.reported_cwnd);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RTT: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn reports_max_cwnd_per_interval() {
+        let gauge = Gauge::default();
+        let mut reporter = CongestionReporter::new(gauge.clone());
+        let start = reporter.last_update;
+
+        reporter.record(1000, RTT, start);
+        reporter.record(3000, RTT, start + Duration::from_millis(10));
+        assert_eq!(gauge.get(), 0);
+
+        // The first interval lasts 50ms
+        reporter.record(2000, RTT, start + Duration::from_millis(50));
+        assert_eq!(gauge.get(), 3000);
+
+        // Later intervals last one RTT, and replace the reported window
+        reporter.record(500, RTT, start + Duration::from_millis(100));
+        assert_eq!(gauge.get(), 3000);
+        reporter.record(400, RTT, start + Duration::from_millis(150));
+        assert_eq!(gauge.get(), 500);
+    }
+
+    #[test]
+    fn connections_share_the_gauge() {
+        let gauge = Gauge::default();
+        let mut a = CongestionReporter::new(gauge.clone());
+        let mut b = CongestionReporter::new(gauge.clone());
+        let later = a.last_update.max(b.last_update) + RTT;
+
+        a.record(1000, RTT, later);
+        b.record(2500, RTT, later);
+        assert_eq!(gauge.get(), 3500);
+
+        drop(a);
+        assert_eq!(gauge.get(), 2500);
+        drop(b);
+        assert_eq!(gauge.get(), 0);
+    }
+}
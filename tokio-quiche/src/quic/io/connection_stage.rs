@@ -98,6 +98,9 @@ where
 #[derive(Debug)]
 pub struct Handshake {
     pub handshake_info: HandshakeInfo,
+    /// Whether the connection was in early data at any point of the
+    /// handshake.
+    pub(crate) saw_early_data: bool,
 }
 
 impl Handshake {
@@ -122,6 +125,8 @@ impl ConnectionStage for Handshake {
         &mut self, qconn: &mut QuicheConnection,
         _ctx: &mut ConnectionStageContext<A>,
     ) -> ControlFlow<QuicResult<()>> {
+        self.saw_early_data |= qconn.is_in_early_data();
+
         if qconn.is_established() {
             ControlFlow::Break(Ok(()))
         } else {
@@ -28,6 +28,8 @@ use std::io;
 use std::net::SocketAddr;
 use std::time::Instant;
 
+use crate::metrics::Metrics;
+
 #[cfg(target_os = "linux")]
 mod linux_imports {
     pub(super) use nix::sys::socket::sendmsg;
@@ -112,10 +114,11 @@ impl PktInfo {
 }
 
 #[cfg(all(target_os = "linux", not(feature = "fuzzing")))]
-pub async fn send_to(
+#[allow(clippy::too_many_arguments)]
+pub async fn send_to<M: Metrics>(
     socket: &tokio::net::UdpSocket, to: SocketAddr, from: Option<SocketAddr>,
     send_buf: &[u8], segment_size: usize, num_pkts: usize,
-    tx_time: Option<Instant>,
+    tx_time: Option<Instant>, metrics: &M,
 ) -> io::Result<usize> {
     // An instant with the value of zero, since [`Instant`] is backed by a version
     // of timespec this allows to extract raw values from an [`Instant`]
@@ -153,24 +156,38 @@ pub async fn send_to(
         // Must use [`try_io`] so tokio can properly clear its readyness flag
         let res = socket.try_io(Interest::WRITABLE, || {
             let fd = socket.as_raw_fd();
-            sendmsg(fd, &iov, &cmsgs, MsgFlags::empty(), Some(&addr))
-                .map_err(Into::into)
+            #[cfg(feature = "perf-quic-listener-metrics")]
+            let start = Instant::now();
+            let res = sendmsg(fd, &iov, &cmsgs, MsgFlags::empty(), Some(&addr));
+            #[cfg(feature = "perf-quic-listener-metrics")]
+            if res.is_ok() {
+                metrics
+                    .udp_send_syscall_duration()
+                    .observe(start.elapsed().as_nanos() as u64);
+            }
+            res.map_err(Into::into)
         });
 
         match res {
             // Wait for the socket to become writable and try again
-            Err(e) if e.kind() == ErrorKind::WouldBlock =>
-                socket.writable().await?,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                let start = Instant::now();
+                socket.writable().await?;
+                metrics
+                    .write_blocked_duration()
+                    .observe(start.elapsed().as_nanos() as u64);
+            },
             res => return res,
         }
     }
 }
 
 #[cfg(any(not(target_os = "linux"), feature = "fuzzing"))]
-pub(crate) async fn send_to(
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn send_to<M: Metrics>(
     socket: &tokio::net::UdpSocket, to: SocketAddr, _from: Option<SocketAddr>,
     send_buf: &[u8], _segment_size: usize, _num_pkts: usize,
-    _tx_time: Option<Instant>,
+    _tx_time: Option<Instant>, _metrics: &M,
 ) -> io::Result<usize> {
     socket.send_to(send_buf, to).await
 }
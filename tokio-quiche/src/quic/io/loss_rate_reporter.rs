@@ -0,0 +1,127 @@
+// Copyright (C) 2025, Cloudflare, Inc.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+// IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+// THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+// PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use foundations::telemetry::metrics::Histogram;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::quic::QuicheConnection;
+
+/// [`LossRateReporter`] periodically samples the packet loss rate of a
+/// connection into a histogram.
+///
+/// Each sample is the percentage of packets that were declared lost among
+/// those sent since the previous sample. Intervals in which no packets were
+/// sent are skipped.
+pub(super) struct LossRateReporter {
+    /// Time of last sample
+    last_sample: Instant,
+    /// Period between samples
+    sample_interval: Duration,
+    /// Packets sent at last sample
+    last_sent: usize,
+    /// Packets lost at last sample
+    last_lost: usize,
+
+    loss_rate_histogram: Histogram,
+}
+
+impl LossRateReporter {
+    pub(super) fn new(
+        loss_rate_histogram: Histogram, sample_interval: Duration,
+    ) -> Self {
+        LossRateReporter {
+            last_sample: Instant::now(),
+            sample_interval,
+            last_sent: 0,
+            last_lost: 0,
+            loss_rate_histogram,
+        }
+    }
+
+    #[inline]
+    pub(super) fn update(&mut self, quiche: &QuicheConnection, now: Instant) {
+        if now.duration_since(self.last_sample) < self.sample_interval {
+            return;
+        }
+
+        let stats = quiche.stats();
+
+        if let Some(loss_rate) = self.sample(stats.sent, stats.lost, now) {
+            self.loss_rate_histogram.observe(loss_rate);
+        }
+    }
+
+    /// Starts a new interval at the given packet totals and returns the loss
+    /// rate of the previous interval, or [`None`] if no packets were sent.
+    fn sample(&mut self, sent: usize, lost: usize, now: Instant) -> Option<f64> {
+        let sent_delta = sent.saturating_sub(self.last_sent);
+        let lost_delta = lost.saturating_sub(self.last_lost);
+
+        self.last_sent = sent;
+        self.last_lost = lost;
+        self.last_sample = now;
+
+        (sent_delta > 0).then(|| lost_delta as f64 * 100. / sent_delta as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reporter() -> LossRateReporter {
+        LossRateReporter::new(
+            Histogram::new(std::iter::empty()),
+            Duration::from_secs(1),
+        )
+    }
+
+    #[test]
+    fn loss_rate_per_interval() {
+        let mut reporter = reporter();
+        let now = Instant::now();
+
+        assert_eq!(reporter.sample(200, 10, now), Some(5.));
+        // Only packets of the latest interval count
+        assert_eq!(reporter.sample(300, 60, now), Some(50.));
+        assert_eq!(reporter.sample(400, 60, now), Some(0.));
+    }
+
+    #[test]
+    fn interval_without_sent_packets_is_skipped() {
+        let mut reporter = reporter();
+        let now = Instant::now();
+
+        assert_eq!(reporter.sample(0, 0, now), None);
+        assert_eq!(reporter.sample(100, 10, now), Some(10.));
+
+        // Packets lost without new packets being sent are not reported, and
+        // are not carried over into the next interval either.
+        assert_eq!(reporter.sample(100, 20, now), None);
+        assert_eq!(reporter.sample(200, 20, now), Some(0.));
+    }
+}
@@ -24,7 +24,11 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+pub(crate) mod cid_reporter;
+pub(crate) mod congestion_reporter;
 pub mod connection_stage;
 pub(crate) mod gso;
+pub(crate) mod loss_rate_reporter;
+pub(crate) mod rtt_reporter;
 pub(crate) mod utilization_estimator;
 pub(crate) mod worker;
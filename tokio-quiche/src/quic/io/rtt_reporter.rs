@@ -0,0 +1,155 @@
+// Copyright (C) 2025, Cloudflare, Inc.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+// IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+// THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+// PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use foundations::telemetry::metrics::Histogram;
+use std::time::Duration;
+
+use crate::quic::QuicheConnection;
+
+/// [`RttReporter`] samples the smoothed RTT of a connection into a histogram
+/// and records the RTT range of the connection when it closes.
+///
+/// quiche only updates the RTT estimate when it processes an ACK, so a new
+/// sample is recorded whenever the estimate changes.
+pub(crate) struct RttReporter {
+    /// RTT recorded by the last sample
+    last_rtt: Option<Duration>,
+    /// Max RTT observed over the lifetime of the connection
+    max_rtt: Option<Duration>,
+
+    rtt_histogram: Histogram,
+    min_rtt_histogram: Histogram,
+    max_rtt_histogram: Histogram,
+}
+
+impl RttReporter {
+    pub(crate) fn new(
+        rtt_histogram: Histogram, min_rtt_histogram: Histogram,
+        max_rtt_histogram: Histogram,
+    ) -> Self {
+        RttReporter {
+            last_rtt: None,
+            max_rtt: None,
+
+            rtt_histogram,
+            min_rtt_histogram,
+            max_rtt_histogram,
+        }
+    }
+
+    #[inline]
+    pub(super) fn update(&mut self, quiche: &QuicheConnection) {
+        let Some((rtt, _)) = Self::active_path_rtt(quiche) else {
+            return;
+        };
+
+        if self.record(rtt) {
+            observe_micros(&self.rtt_histogram, rtt);
+        }
+    }
+
+    /// Records `rtt` if the estimate changed since the last sample, and
+    /// returns whether it did.
+    fn record(&mut self, rtt: Duration) -> bool {
+        if self.last_rtt == Some(rtt) {
+            return false;
+        }
+
+        self.last_rtt = Some(rtt);
+        self.max_rtt = self.max_rtt.max(Some(rtt));
+        true
+    }
+
+    /// Max RTT over the lifetime of the connection, including the final
+    /// estimate `rtt`.
+    fn max_rtt(&self, rtt: Duration) -> Duration {
+        self.max_rtt.map_or(rtt, |max| max.max(rtt))
+    }
+
+    /// Records the final RTT estimate and the RTT range of a closing
+    /// connection.
+    pub(super) fn on_close(&mut self, quiche: &QuicheConnection) {
+        let Some((rtt, min_rtt)) = Self::active_path_rtt(quiche) else {
+            return;
+        };
+
+        let max_rtt = self.max_rtt(rtt);
+
+        observe_micros(&self.rtt_histogram, rtt);
+        observe_micros(&self.min_rtt_histogram, min_rtt);
+        observe_micros(&self.max_rtt_histogram, max_rtt);
+    }
+
+    /// Returns the smoothed and minimum RTT of the active path, if it has
+    /// been measured.
+    fn active_path_rtt(
+        quiche: &QuicheConnection,
+    ) -> Option<(Duration, Duration)> {
+        let path = quiche.path_stats().find(|s| s.active)?;
+
+        // Until the first RTT sample, `rtt` is the configured initial RTT
+        path.min_rtt.map(|min_rtt| (path.rtt, min_rtt))
+    }
+}
+
+fn observe_micros(histogram: &Histogram, rtt: Duration) {
+    histogram.observe(rtt.as_micros() as f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reporter() -> RttReporter {
+        let histogram = || Histogram::new(std::iter::empty());
+        RttReporter::new(histogram(), histogram(), histogram())
+    }
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn samples_changed_estimates() {
+        let mut reporter = reporter();
+
+        assert!(reporter.record(ms(30)));
+        assert!(!reporter.record(ms(30)));
+        assert!(reporter.record(ms(20)));
+        assert!(reporter.record(ms(30)));
+    }
+
+    #[test]
+    fn max_rtt_includes_final_estimate() {
+        let mut reporter = reporter();
+        assert_eq!(reporter.max_rtt(ms(25)), ms(25));
+
+        reporter.record(ms(40));
+        reporter.record(ms(20));
+        assert_eq!(reporter.max_rtt(ms(25)), ms(40));
+        assert_eq!(reporter.max_rtt(ms(50)), ms(50));
+    }
+}
@@ -25,6 +25,7 @@
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use foundations::telemetry::metrics::Gauge;
+use foundations::telemetry::metrics::Histogram;
 
 use std::collections::VecDeque;
 use std::ops::Div;
@@ -36,6 +37,9 @@ use crate::quic::QuicheConnection;
 
 const EST_WIN: usize = 10;
 
+/// Interval between two samples of the bandwidth estimate.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
 /// [`BandwidthReporter`] is responsible to track the bandwidth estimate for the
 /// connection
 pub(super) struct BandwidthReporter {
@@ -56,13 +60,17 @@ pub(super) struct BandwidthReporter {
     /// Loss at max recorded bandwidth
     pub(super) max_loss_pct: f32,
 
+    /// Time of last bandwidth sample
+    last_sample: Instant,
+
     estimator: MaxUtilizedBandwidthEstimator,
 
     gauge: Gauge,
+    histogram: Histogram,
 }
 
 impl BandwidthReporter {
-    pub(super) fn new(gauge: Gauge) -> Self {
+    pub(super) fn new(gauge: Gauge, histogram: Histogram) -> Self {
         BandwidthReporter {
             last_update: Instant::now(),
             update_period: Duration::from_millis(50),
@@ -76,9 +84,12 @@ impl BandwidthReporter {
             max_bandwidth: 0,
             max_loss_pct: 0.,
 
+            last_sample: Instant::now(),
+
             estimator: MaxUtilizedBandwidthEstimator::new(),
 
             gauge,
+            histogram,
         }
     }
 
@@ -120,6 +131,13 @@ impl BandwidthReporter {
             self.max_loss_pct = self.max_loss_pct.max(bw_estimate.loss);
         }
 
+        if self.last_bandwidth > 0 &&
+            now.duration_since(self.last_sample) >= SAMPLE_INTERVAL
+        {
+            self.histogram.observe(self.last_bandwidth as f64 * 1e-6);
+            self.last_sample = now;
+        }
+
         if let Some(p) = quiche.path_stats().find(|s| s.active) {
             self.update_period = p.rtt;
         }
@@ -24,6 +24,7 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::ops::ControlFlow;
 use std::sync::Arc;
@@ -33,12 +34,16 @@ use std::time::Instant;
 #[cfg(feature = "perf-quic-listener-metrics")]
 use std::time::SystemTime;
 
+use super::cid_reporter::CidReporter;
+use super::congestion_reporter::CongestionReporter;
 use super::connection_stage::Close;
 use super::connection_stage::ConnectionStage;
 use super::connection_stage::ConnectionStageContext;
 use super::connection_stage::Handshake;
 use super::connection_stage::RunningApplication;
 use super::gso::*;
+use super::loss_rate_reporter::LossRateReporter;
+use super::rtt_reporter::RttReporter;
 use super::utilization_estimator::BandwidthReporter;
 
 use crate::metrics::labels;
@@ -57,6 +62,7 @@ use datagram_socket::DatagramSocketSendExt;
 use datagram_socket::MaybeConnectedSocket;
 use datagram_socket::QuicAuditStats;
 use foundations::telemetry::log;
+use foundations::telemetry::metrics::Histogram;
 use quiche::ConnectionId;
 use quiche::Error as QuicheError;
 use quiche::SendInfo;
@@ -76,12 +82,18 @@ const RELEASE_TIMER_THRESHOLD: Duration = Duration::from_micros(250);
 /// Stop queuing GSO packets, if packet size is below this threshold.
 const GSO_THRESHOLD: usize = 1_000;
 
+/// Minimum interval between two updates of the connection's shared
+/// [`QuicConnectionStats`].
+const STATS_REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
 pub struct WriterConfig {
     pub pending_cid: Option<ConnectionId<'static>>,
     pub peer_addr: SocketAddr,
     pub with_gso: bool,
     pub pacing_offload: bool,
     pub with_pktinfo: bool,
+    pub enable_sni_metrics: bool,
+    pub loss_rate_sample_interval: Duration,
 }
 
 #[derive(Default)]
@@ -109,6 +121,10 @@ pub(crate) struct IoWorkerParams<Tx, M> {
     pub(crate) conn_map_cmd_tx: mpsc::UnboundedSender<ConnectionMapCommand>,
     #[cfg(feature = "perf-quic-listener-metrics")]
     pub(crate) init_rx_time: Option<SystemTime>,
+    /// Time at which the connection's first Initial packet was accepted.
+    pub(crate) start_time: Instant,
+    /// Carried across stages to track the RTT range of the connection.
+    pub(crate) rtt_reporter: RttReporter,
     pub(crate) metrics: M,
 }
 
@@ -124,9 +140,26 @@ pub(crate) struct IoWorker<Tx, M, S> {
     conn_map_cmd_tx: mpsc::UnboundedSender<ConnectionMapCommand>,
     #[cfg(feature = "perf-quic-listener-metrics")]
     init_rx_time: Option<SystemTime>,
+    start_time: Instant,
     metrics: M,
     conn_stage: S,
     bw_estimator: BandwidthReporter,
+    cc_reporter: CongestionReporter,
+    cid_reporter: CidReporter,
+    loss_rate_reporter: LossRateReporter,
+    rtt_reporter: RttReporter,
+    metrics_sent_packet_size: Histogram,
+    metrics_received_packet_size: Histogram,
+    metrics_gso_batch_size: Histogram,
+    metrics_packet_coalescing_ratio: Histogram,
+    /// Start times of validations for paths announced by
+    /// [`quiche::PathEvent::New`], keyed by (local, peer) address
+    pending_path_validations: HashMap<(SocketAddr, SocketAddr), Instant>,
+    /// Total length of the 0-RTT packets received by a server, or sent by a
+    /// client, during the handshake
+    early_data_bytes: u64,
+    /// Time at which the connection's shared stats were last updated
+    stats_refreshed_at: Option<Instant>,
 }
 
 impl<Tx, M, S> IoWorker<Tx, M, S>
@@ -136,8 +169,23 @@ where
     S: ConnectionStage,
 {
     pub(crate) fn new(params: IoWorkerParams<Tx, M>, conn_stage: S) -> Self {
-        let bw_estimator =
-            BandwidthReporter::new(params.metrics.utilized_bandwidth());
+        let bw_estimator = BandwidthReporter::new(
+            params.metrics.utilized_bandwidth(),
+            params.metrics.bandwidth_estimate_mbps(),
+        );
+        let cc_reporter = CongestionReporter::new(params.metrics.cwnd_bytes());
+        let cid_reporter =
+            CidReporter::new(params.metrics.active_connection_id_count());
+        let loss_rate_reporter = LossRateReporter::new(
+            params.metrics.packet_loss_rate_pct(),
+            params.cfg.loss_rate_sample_interval,
+        );
+        let metrics_sent_packet_size = params.metrics.sent_packet_size_bytes();
+        let metrics_received_packet_size =
+            params.metrics.received_packet_size_bytes();
+        let metrics_gso_batch_size = params.metrics.gso_batch_size();
+        let metrics_packet_coalescing_ratio =
+            params.metrics.packet_coalescing_ratio();
 
         log::trace!("Creating IoWorker with stage: {conn_stage:?}");
 
@@ -150,9 +198,21 @@ where
             conn_map_cmd_tx: params.conn_map_cmd_tx,
             #[cfg(feature = "perf-quic-listener-metrics")]
             init_rx_time: params.init_rx_time,
+            start_time: params.start_time,
             metrics: params.metrics,
             conn_stage,
             bw_estimator,
+            cc_reporter,
+            cid_reporter,
+            loss_rate_reporter,
+            rtt_reporter: params.rtt_reporter,
+            metrics_sent_packet_size,
+            metrics_received_packet_size,
+            metrics_gso_batch_size,
+            metrics_packet_coalescing_ratio,
+            pending_path_validations: HashMap::new(),
+            early_data_bytes: 0,
+            stats_refreshed_at: None,
         }
     }
 
@@ -222,6 +282,16 @@ where
             }
 
             self.bw_estimator.update(qconn, now);
+            self.cc_reporter.update(qconn, now);
+            self.cid_reporter.update(qconn, now);
+            self.loss_rate_reporter.update(qconn, now);
+            self.rtt_reporter.update(qconn);
+            self.report_path_events(qconn);
+
+            if ctx.application.should_act() {
+                ctx.application.report_metrics(&self.metrics);
+            }
+            self.refresh_stats(qconn, ctx, now);
 
             let new_deadline = min_of_some(
                 qconn.timeout_instant(),
@@ -272,7 +342,18 @@ where
     fn gather_data_from_quiche_conn(
         &mut self, qconn: &mut QuicheConnection, send_buf: &mut [u8],
     ) -> QuicResult<usize> {
-        self.fill_send_buffer(qconn, send_buf)
+        let packets_before = qconn.stats().sent;
+        let res = self.fill_send_buffer(qconn, send_buf);
+
+        // Each datagram holds one or more coalesced QUIC packets
+        let datagrams = self.write_state.num_pkts;
+        if datagrams > 0 {
+            let packets = qconn.stats().sent - packets_before;
+            self.metrics_packet_coalescing_ratio
+                .observe(packets as f64 / datagrams as f64);
+        }
+
+        res
     }
 
     #[cfg(feature = "perf-quic-listener-metrics")]
@@ -448,6 +529,11 @@ where
 
                 self.write_state.bytes_written += packet_size;
                 self.write_state.num_pkts += 1;
+                self.metrics_sent_packet_size.observe(packet_size as f64);
+                if !qconn.is_server() && !qconn.is_established() {
+                    self.early_data_bytes +=
+                        zero_rtt_len(&send_buf[..packet_size]);
+                }
                 self.write_state.send_from =
                     send_info.as_ref().map(|info| info.from);
 
@@ -496,6 +582,9 @@ where
                 (self.socket.as_udp_socket(), self.cfg.with_gso)
             {
                 // Only UDP supports GSO
+                self.metrics_gso_batch_size
+                    .observe(self.write_state.num_pkts as f64);
+
                 send_to(
                     udp_socket,
                     self.cfg.peer_addr,
@@ -504,6 +593,7 @@ where
                     self.write_state.segment_size,
                     self.write_state.num_pkts,
                     self.write_state.tx_time,
+                    &self.metrics,
                 )
                 .await
             } else {
@@ -529,6 +619,69 @@ where
         }
     }
 
+    /// Updates the connection's shared [`QuicConnectionStats`], unless they
+    /// were updated less than [`STATS_REFRESH_INTERVAL`] ago.
+    fn refresh_stats<A: ApplicationOverQuic>(
+        &mut self, qconn: &QuicheConnection, ctx: &ConnectionStageContext<A>,
+        now: Instant,
+    ) {
+        if self
+            .stats_refreshed_at
+            .is_some_and(|last| now.duration_since(last) < STATS_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.stats_refreshed_at = Some(now);
+
+        let streams_open = ctx.application.streams_open();
+        *ctx.stats.lock().unwrap() =
+            QuicConnectionStats::from_conn(qconn, streams_open);
+    }
+
+    /// Drains quiche's path events and records path validations and
+    /// connection migrations.
+    fn report_path_events(&mut self, qconn: &mut QuicheConnection) {
+        while let Some(event) = qconn.path_event_next() {
+            match event {
+                // quiche sends a PATH_CHALLENGE on new paths right away, so
+                // validation starts when the path is announced
+                quiche::PathEvent::New(local, peer) => {
+                    self.pending_path_validations
+                        .insert((local, peer), Instant::now());
+                },
+                quiche::PathEvent::Validated(local, peer) => {
+                    if let Some(start) =
+                        self.pending_path_validations.remove(&(local, peer))
+                    {
+                        self.metrics
+                            .path_validation_duration()
+                            .observe(start.elapsed().as_nanos() as u64);
+                    }
+                },
+                quiche::PathEvent::Closed(local, peer) => {
+                    self.pending_path_validations.remove(&(local, peer));
+                },
+                quiche::PathEvent::PeerMigrated(..) => {
+                    self.metrics.connection_migration_success_count().inc();
+                    self.metrics
+                        .connection_attempt_count(
+                            labels::ConnectionAttemptOutcome::MigrationSucceeded,
+                        )
+                        .inc();
+                },
+                quiche::PathEvent::FailedValidation(local, peer) => {
+                    self.pending_path_validations.remove(&(local, peer));
+                    self.metrics
+                        .connection_migration_failure_count(
+                            labels::MigrationFailureReason::ValidationFailed,
+                        )
+                        .inc();
+                },
+                _ => {},
+            }
+        }
+    }
+
     /// Process the incoming packet
     fn process_incoming(
         &mut self, qconn: &mut QuicheConnection, mut pkt: Incoming,
@@ -538,11 +691,24 @@ where
             to: pkt.local_addr,
         };
 
+        // Only servers receive 0-RTT packets
+        let count_early_data = qconn.is_server() && !qconn.is_established();
+
         if let Some(gro) = pkt.gro {
             for dgram in pkt.buf.chunks_mut(gro as usize) {
+                self.metrics_received_packet_size
+                    .observe(dgram.len() as f64);
+                if count_early_data {
+                    self.early_data_bytes += zero_rtt_len(dgram);
+                }
                 qconn.recv(dgram, recv_info)?;
             }
         } else {
+            self.metrics_received_packet_size
+                .observe(pkt.buf.len() as f64);
+            if count_early_data {
+                self.early_data_bytes += zero_rtt_len(&pkt.buf);
+            }
             qconn.recv(&mut pkt.buf, recv_info)?;
         }
 
@@ -659,6 +825,15 @@ where
 
         if let Err(err) = &work_loop_result {
             self.metrics.failed_handshakes(err.into()).inc();
+            self.metrics
+                .connection_attempt_count(
+                    labels::ConnectionAttemptOutcome::HandshakeFailed,
+                )
+                .inc();
+            if let Some(reason) = labels::HandshakeAbortReason::from_error(err) {
+                self.metrics.handshake_abort_reason_count(reason).inc();
+            }
+            self.record_tls_alerts(&qconn);
 
             return RunningOrClosing::Closing(Closing {
                 params: self.into(),
@@ -689,6 +864,80 @@ where
         }
     }
 
+    /// Records the TLS alerts which were sent or received in `CRYPTO_ERROR`
+    /// frames.
+    fn record_tls_alerts(&self, qconn: &QuicheConnection) {
+        let errors = [qconn.local_error(), qconn.peer_error()];
+
+        for err in errors.into_iter().flatten().filter(|e| !e.is_app) {
+            if let Some(code) =
+                labels::TlsAlertCode::from_crypto_error(err.error_code)
+            {
+                self.metrics.tls_alert_count(code).inc();
+            }
+        }
+    }
+
+    /// Records the outcome of 0-RTT for a newly established connection.
+    fn record_zero_rtt(&self, qconn: &QuicheConnection) {
+        if qconn.is_server() {
+            if self.conn_stage.saw_early_data {
+                self.metrics.zero_rtt_accepted_count().inc();
+                self.metrics
+                    .early_data_size_bytes()
+                    .observe(self.early_data_bytes as f64);
+            } else if self.early_data_bytes > 0 {
+                self.metrics.zero_rtt_rejected_count().inc();
+            }
+        } else if self.early_data_bytes > 0 {
+            self.metrics.zero_rtt_attempt_count().inc();
+
+            // quiche doesn't tell clients whether their early data was
+            // accepted, but it can't be without resuming the session
+            if !qconn.is_resumed() {
+                self.metrics.zero_rtt_rejected_count().inc();
+            }
+        }
+    }
+
+    /// Records the length of the certificate chain presented by the peer.
+    fn record_certificate_chain_depth(&self, qconn: &QuicheConnection) {
+        if let Some(chain) = qconn.peer_cert_chain() {
+            self.metrics
+                .certificate_chain_depth()
+                .observe(chain.len() as f64);
+        }
+    }
+
+    /// Records whether a client connection resumed a TLS session.
+    fn record_session_resumption(&self, qconn: &QuicheConnection) {
+        if !qconn.is_server() && qconn.is_resumed() {
+            self.metrics.tls_session_resumption_succeeded().inc();
+        }
+    }
+
+    /// Records the `max_ack_delay` transport parameter advertised by the peer.
+    fn record_peer_max_ack_delay(&self, qconn: &QuicheConnection) {
+        if let Some(params) = qconn.peer_transport_params() {
+            // The transport parameter is in milliseconds
+            self.metrics
+                .max_ack_delay_micros()
+                .observe(params.max_ack_delay as f64 * 1000.);
+        }
+    }
+
+    /// Records the number of packets it took to complete the handshake.
+    fn record_handshake_packets(&self, qconn: &QuicheConnection) {
+        let stats = qconn.stats();
+
+        self.metrics
+            .handshake_packet_count(labels::FrameDirection::Sent)
+            .observe(stats.sent as f64);
+        self.metrics
+            .handshake_packet_count(labels::FrameDirection::Received)
+            .observe(stats.recv as f64);
+    }
+
     fn on_conn_established<App: ApplicationOverQuic>(
         &mut self, qconn: &mut QuicheConnection, driver: &mut App,
     ) -> QuicResult<()> {
@@ -701,6 +950,23 @@ where
 
             self.audit_log_stats
                 .set_transport_handshake_duration(handshake_info.elapsed());
+            self.metrics.handshake_success_count().inc();
+            self.metrics
+                .connection_attempt_count(
+                    labels::ConnectionAttemptOutcome::HandshakeCompleted,
+                )
+                .inc();
+            self.record_zero_rtt(qconn);
+            self.record_certificate_chain_depth(qconn);
+            self.record_handshake_packets(qconn);
+            self.record_session_resumption(qconn);
+            self.record_peer_max_ack_delay(qconn);
+
+            if self.cfg.enable_sni_metrics {
+                if let Some(sni) = qconn.server_name() {
+                    self.metrics.sni_count(Arc::from(sni)).inc();
+                }
+            }
 
             driver.on_conn_established(qconn, handshake_info)?;
         }
@@ -726,6 +992,8 @@ impl<Tx, M, S> From<IoWorker<Tx, M, S>> for IoWorkerParams<Tx, M> {
             conn_map_cmd_tx: value.conn_map_cmd_tx,
             #[cfg(feature = "perf-quic-listener-metrics")]
             init_rx_time: value.init_rx_time,
+            start_time: value.start_time,
+            rtt_reporter: value.rtt_reporter,
             metrics: value.metrics,
         }
     }
@@ -773,6 +1041,8 @@ where
                 .observe(self.bw_estimator.max_loss_pct as f64 * 100.);
         }
 
+        self.rtt_reporter.on_close(qconn);
+
         if ctx.application.should_act() {
             ctx.application.on_conn_close(
                 qconn,
@@ -788,7 +1058,10 @@ where
         let _ = self.gather_data_from_quiche_conn(qconn, ctx.buffer());
         self.flush_buffer_to_socket(ctx.buffer()).await;
 
-        *ctx.stats.lock().unwrap() = QuicConnectionStats::from_conn(qconn);
+        // Open streams are closed along with the connection
+        let streams_open = ctx.application.streams_open().map(|_| 0);
+        *ctx.stats.lock().unwrap() =
+            QuicConnectionStats::from_conn(qconn, streams_open);
 
         if let Some(err) = qconn.peer_error() {
             if err.is_app {
@@ -804,6 +1077,14 @@ where
             }
         }
 
+        if qconn.is_timed_out() {
+            self.metrics.connection_idle_timeout_count().inc();
+        }
+
+        if qconn.is_established() {
+            self.record_close_outcome(qconn);
+        }
+
         self.close_connection(qconn);
 
         if let Err(work_loop_error) = self.conn_stage.work_loop_result {
@@ -812,6 +1093,23 @@ where
         }
     }
 
+    /// Records how an established connection ended.
+    fn record_close_outcome(&self, qconn: &QuicheConnection) {
+        use labels::ConnectionAttemptOutcome::*;
+
+        let outcome = if qconn.is_timed_out() {
+            IdleTimeout
+        } else {
+            match qconn.local_error().or(qconn.peer_error()) {
+                Some(err) if err.is_app => ApplicationClose,
+                Some(_) => TransportError,
+                None => return,
+            }
+        };
+
+        self.metrics.connection_attempt_count(outcome).inc();
+    }
+
     fn close_connection(&mut self, qconn: &QuicheConnection) {
         let scid = qconn.source_id().into_owned();
 
@@ -825,10 +1123,118 @@ where
             .conn_map_cmd_tx
             .send(ConnectionMapCommand::RemoveScid(scid));
 
+        let stats = qconn.stats();
+        // quiche-mallard does not count DATAGRAM frames
+        #[cfg(not(feature = "gcongestion"))]
+        {
+            self.metrics
+                .datagram_send_count()
+                .inc_by(stats.dgram_sent as u64);
+            self.metrics
+                .datagram_recv_count()
+                .inc_by(stats.dgram_recv as u64);
+        }
+        self.metrics
+            .bytes_retransmitted()
+            .inc_by(stats.stream_retrans_bytes);
+
+        // quiche only exposes counts for a few frame types, and only once per
+        // stream rather than per frame sent, so retransmissions are not counted
+        use labels::FrameDirection::*;
+        use labels::QuicFrameType::*;
+        let frame_counts = [
+            (ResetStream, Sent, stats.reset_stream_count_local),
+            (ResetStream, Received, stats.reset_stream_count_remote),
+            (StopSending, Sent, stats.stopped_stream_count_local),
+            (StopSending, Received, stats.stopped_stream_count_remote),
+            (PathChallenge, Received, stats.path_challenge_rx_count),
+            #[cfg(not(feature = "gcongestion"))]
+            (Datagram, Sent, stats.dgram_sent as u64),
+            #[cfg(not(feature = "gcongestion"))]
+            (Datagram, Received, stats.dgram_recv as u64),
+        ];
+        for (frame_type, direction, count) in frame_counts {
+            self.metrics
+                .frame_count(frame_type, direction)
+                .inc_by(count);
+        }
+
+        let close_frames =
+            [(qconn.local_error(), Sent), (qconn.peer_error(), Received)];
+        for (err, direction) in close_frames {
+            if let Some(err) = err {
+                self.metrics
+                    .connection_close_frame_type_count(err.into(), direction)
+                    .inc();
+            }
+        }
+
         self.metrics.connections_in_memory().dec();
+        self.metrics
+            .memory_usage_bytes(labels::MemoryCategory::ConnectionState)
+            .dec_by(size_of::<QuicheConnection>() as u64);
+        self.metrics
+            .connection_duration_seconds()
+            .observe(self.start_time.elapsed().as_nanos() as u64);
     }
 }
 
+/// Returns the total length of the 0-RTT packets coalesced in `dgram`.
+fn zero_rtt_len(dgram: &[u8]) -> u64 {
+    let mut b = octets::Octets::with_slice(dgram);
+    let mut total = 0;
+
+    while let Some((is_zero_rtt, len)) = next_long_header_packet(&mut b) {
+        if is_zero_rtt {
+            total += len;
+        }
+    }
+
+    total
+}
+
+/// Skips over the next long header packet in `b`, returning whether it is a
+/// 0-RTT packet and the length of its packet number and payload.
+///
+/// Returns `None` if there are no more long header packets to parse.
+fn next_long_header_packet(b: &mut octets::Octets) -> Option<(bool, u64)> {
+    const QUIC_V2: u32 = 0x6b33_43cf;
+
+    let first = b.get_u8().ok()?;
+    if first & 0x80 == 0 {
+        return None;
+    }
+
+    // Version Negotiation packets have no length field
+    let version = b.get_u32().ok()?;
+    if version == 0 {
+        return None;
+    }
+
+    let (initial, zero_rtt, retry) = if version == QUIC_V2 {
+        (1, 2, 0)
+    } else {
+        (0, 1, 3)
+    };
+
+    // Retry packets have no length field either
+    let ty = (first & 0x30) >> 4;
+    if ty == retry {
+        return None;
+    }
+
+    b.get_bytes_with_u8_length().ok()?; // DCID
+    b.get_bytes_with_u8_length().ok()?; // SCID
+    if ty == initial {
+        b.get_bytes_with_varint_length().ok()?; // Token
+    }
+
+    let len = b.get_varint().ok()?;
+    b.skip(len as usize).ok()?;
+
+    Some((ty == zero_rtt, len))
+}
+
 /// Returns the minimum of `v1` and `v2`, ignoring `None`s.
 fn min_of_some<T: Ord>(v1: Option<T>, v2: Option<T>) -> Option<T> {
     match (v1, v2) {
@@ -837,3 +1243,27 @@ fn min_of_some<T: Ord>(v1: Option<T>, v2: Option<T>) -> Option<T> {
         (None, None) => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_rtt_len_of_coalesced_packets() {
+        let mut dgram = vec![];
+
+        // Initial: DCID, SCID, token, then 4 bytes of packet number and payload
+        dgram.extend([0xc0, 0, 0, 0, 1, 2, 0xaa, 0xbb, 1, 0xcc, 1, 0xdd, 4]);
+        dgram.extend([0; 4]);
+        // 0-RTT with 10 bytes of packet number and payload
+        dgram.extend([0xd0, 0, 0, 0, 1, 1, 0xaa, 0, 10]);
+        dgram.extend([0; 10]);
+        // Padding after the last packet
+        dgram.extend([0; 8]);
+
+        assert_eq!(zero_rtt_len(&dgram), 10);
+
+        // A short header packet is never followed by another packet
+        assert_eq!(zero_rtt_len(&[0x40, 0xd0, 0, 0, 0, 1]), 0);
+    }
+}
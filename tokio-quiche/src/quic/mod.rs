@@ -120,6 +120,7 @@ pub use self::connection::ConnectionShutdownBehaviour;
 pub use self::connection::HandshakeError;
 pub use self::connection::HandshakeInfo;
 pub use self::connection::Incoming;
+pub use self::connection::MetricsSnapshot;
 pub use self::connection::QuicCommand;
 pub use self::connection::QuicConnectionStats;
 pub use self::connection::SimpleConnectionIdGenerator;
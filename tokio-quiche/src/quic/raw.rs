@@ -51,6 +51,7 @@ use crate::metrics::Metrics;
 use crate::quic::HandshakeInfo;
 use crate::quic::Incoming;
 use crate::quic::QuicheConnection;
+use crate::settings::QuicSettings;
 use crate::socket::Socket;
 
 /// Result of manually wrapping a [`quiche::Connection`] in an
@@ -112,6 +113,9 @@ where
         with_gso: false,
         pacing_offload: false,
         with_pktinfo: false,
+        enable_sni_metrics: false,
+        loss_rate_sample_interval:
+            QuicSettings::default_loss_rate_sample_interval(),
     };
 
     let conn_params = QuicConnectionParams {
@@ -149,6 +149,8 @@ where
         let socket = Arc::clone(&self.socket);
         #[cfg(target_os = "linux")]
         let with_pktinfo = self.config.with_pktinfo;
+        #[cfg(target_os = "linux")]
+        let metrics = self.metrics.clone();
 
         spawn_with_killswitch(async move {
             let send_buf = &send_buf[..written];
@@ -164,7 +166,7 @@ where
             {
                 let from = Some(incoming.local_addr).filter(|_| with_pktinfo);
                 let _ = crate::quic::io::gso::send_to(
-                    udp, to, from, send_buf, 1, 1, None,
+                    udp, to, from, send_buf, 1, 1, None, &metrics,
                 )
                 .await;
             }
@@ -183,10 +185,13 @@ where
 
         let token = self.token_manager.gen(&hdr.dcid, incoming.peer_addr);
 
-        self.handshake_reply(incoming, move |buf| {
+        let reply = self.handshake_reply(incoming, move |buf| {
             quiche::retry(&hdr.scid, &hdr.dcid, &scid, &token, hdr.version, buf)
                 .into_io()
-        })
+        })?;
+
+        self.metrics.retry_packet_sent_count().inc();
+        Ok(reply)
     }
 
     fn new_connection_id(&self) -> ConnectionId<'static> {
@@ -211,15 +216,28 @@ where
                 .verify_connection_id(self.socket_cookie, &hdr.dcid)
             {
                 self.metrics.invalid_cid_packet_count(e).inc();
+                self.metrics
+                    .connection_error_before_handshake_count(
+                        labels::PreHandshakeError::InvalidCid,
+                    )
+                    .inc();
             }
 
             Err(labels::QuicInvalidInitialPacketError::WrongType(hdr.ty))?;
         }
 
         if !quiche::version_is_supported(hdr.version) {
-            return self.handshake_reply(incoming, |buf| {
+            let reply = self.handshake_reply(incoming, |buf| {
                 quiche::negotiate_version(&hdr.scid, &hdr.dcid, buf).into_io()
-            });
+            })?;
+
+            self.metrics.version_negotiation_sent_count().inc();
+            self.metrics
+                .connection_error_before_handshake_count(
+                    labels::PreHandshakeError::VersionMismatch,
+                )
+                .inc();
+            return Ok(reply);
         }
 
         let (scid, original_dcid, pending_cid) = if self
@@ -235,25 +253,34 @@ where
                 return self.stateless_retry(incoming, hdr);
             }
 
+            let validation_start = Instant::now();
+            let original_dcid = self
+                .token_manager
+                .validate_and_extract_original_dcid(token, incoming.peer_addr);
+            self.metrics
+                .token_validation_duration()
+                .observe(validation_start.elapsed().as_nanos() as u64);
+
             (
                 hdr.dcid,
-                Some(
-                    self.token_manager
-                        .validate_and_extract_original_dcid(token, incoming.peer_addr)
-                        .or(Err(
-                            labels::QuicInvalidInitialPacketError::TokenValidationFail,
-                        ))?,
-                ),
+                Some(original_dcid.or(Err(
+                    labels::QuicInvalidInitialPacketError::TokenValidationFail,
+                ))?),
                 None,
             )
         };
 
-        self.accept_conn(
+        let new_conn = self.accept_conn(
             incoming,
             scid,
             original_dcid.as_ref(),
             pending_cid,
             quiche_config,
-        )
+        )?;
+
+        self.metrics
+            .negotiated_version_count(hdr.version.into())
+            .inc();
+        Ok(new_conn)
     }
 }
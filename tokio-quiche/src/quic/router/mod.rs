@@ -26,6 +26,8 @@
 
 pub(crate) mod acceptor;
 pub(crate) mod connector;
+#[cfg(target_os = "linux")]
+mod socket_buffers;
 
 use super::connection::ConnectionMap;
 use super::connection::HandshakeInfo;
@@ -40,13 +42,15 @@ use crate::metrics::labels;
 use crate::metrics::quic_expensive_metrics_ip_reduce;
 use crate::metrics::Metrics;
 use crate::settings::Config;
+#[cfg(target_os = "linux")]
+use socket_buffers::SocketBufferReporter;
 
 use datagram_socket::DatagramSocketRecv;
 use datagram_socket::DatagramSocketSend;
 use foundations::telemetry::log;
 #[cfg(target_os = "linux")]
 use foundations::telemetry::metrics::Counter;
-#[cfg(target_os = "linux")]
+use foundations::telemetry::metrics::Gauge;
 use foundations::telemetry::metrics::TimeHistogram;
 #[cfg(target_os = "linux")]
 use libc::sockaddr_in;
@@ -69,6 +73,7 @@ use std::time::Instant;
 use std::time::SystemTime;
 use task_killswitch::spawn_with_killswitch;
 use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
 
 type ConnStream<Tx, M> = mpsc::Receiver<io::Result<InitialQuicConnection<Tx, M>>>;
 
@@ -160,6 +165,15 @@ where
     metrics_handshake_time_seconds: TimeHistogram,
     #[cfg(target_os = "linux")]
     metrics_udp_drop_count: Counter,
+    #[cfg(target_os = "linux")]
+    socket_buffer_reporter: SocketBufferReporter,
+    #[cfg(feature = "perf-quic-listener-metrics")]
+    metrics_packet_dispatch_latency: TimeHistogram,
+    #[cfg(all(target_os = "linux", feature = "perf-quic-listener-metrics"))]
+    metrics_udp_recv_syscall_duration: TimeHistogram,
+    metrics_accept_queue_depth: Gauge,
+    /// Accept queue depth last added to `metrics_accept_queue_depth`
+    accept_queue_depth: u64,
 }
 
 impl<Tx, Rx, M, I> InboundPacketRouter<Tx, Rx, M, I>
@@ -206,6 +220,17 @@ where
                 metrics_handshake_time_seconds: metrics.handshake_time_seconds(labels::QuicHandshakeStage::QueueWaiting),
                 #[cfg(target_os = "linux")]
                 metrics_udp_drop_count: metrics.udp_drop_count(),
+                #[cfg(target_os = "linux")]
+                socket_buffer_reporter: SocketBufferReporter::new(
+                    metrics.send_buffer_bytes(),
+                    metrics.recv_buffer_bytes(),
+                ),
+                #[cfg(feature = "perf-quic-listener-metrics")]
+                metrics_packet_dispatch_latency: metrics.packet_dispatch_latency(),
+                #[cfg(all(target_os = "linux", feature = "perf-quic-listener-metrics"))]
+                metrics_udp_recv_syscall_duration: metrics.udp_recv_syscall_duration(),
+                metrics_accept_queue_depth: metrics.accept_queue_depth(),
+                accept_queue_depth: 0,
 
                 metrics,
 
@@ -216,16 +241,28 @@ where
 
     fn on_incoming(&mut self, mut incoming: Incoming) -> io::Result<()> {
         #[cfg(feature = "perf-quic-listener-metrics")]
-        let start = std::time::Instant::now();
+        let start = Instant::now();
 
         if let Some(dcid) = short_dcid(&incoming.buf) {
             if let Some(ev_sender) = self.conns.get(&dcid) {
-                let _ = ev_sender.try_send(incoming);
+                if let Err(TrySendError::Full(_)) = ev_sender.try_send(incoming) {
+                    self.metrics.receive_queue_overflow_count().inc();
+                }
+                #[cfg(feature = "perf-quic-listener-metrics")]
+                self.metrics_packet_dispatch_latency
+                    .observe(start.elapsed().as_nanos() as u64);
                 return Ok(());
             }
         }
 
         let hdr = Header::from_slice(&mut incoming.buf, MAX_CONN_ID_LEN)
+            .inspect_err(|_| {
+                self.metrics
+                    .connection_error_before_handshake_count(
+                        labels::PreHandshakeError::ParseError,
+                    )
+                    .inc();
+            })
             .map_err(|e| match e {
                 quiche::Error::BufferTooShort =>
                     labels::QuicInvalidInitialPacketError::SmallPacket.into(),
@@ -233,10 +270,19 @@ where
             })?;
 
         if let Some(ev_sender) = self.conns.get(&hdr.dcid) {
-            let _ = ev_sender.try_send(incoming);
+            if let Err(TrySendError::Full(_)) = ev_sender.try_send(incoming) {
+                self.metrics.receive_queue_overflow_count().inc();
+            }
+            #[cfg(feature = "perf-quic-listener-metrics")]
+            self.metrics_packet_dispatch_latency
+                .observe(start.elapsed().as_nanos() as u64);
             return Ok(());
         }
 
+        if hdr.ty == quiche::Type::VersionNegotiation {
+            self.metrics.version_negotiation_received_count().inc();
+        }
+
         #[cfg(feature = "perf-quic-listener-metrics")]
         let _timer = listener_stage_timer::ListenerStageTimer::new(
             start,
@@ -294,6 +340,16 @@ where
         };
         let Ok(send_permit) = self.accept_sink.try_reserve() else {
             // drop the connection if the backlog is full. the client will retry.
+            self.metrics
+                .server_overload_rejection_count(
+                    labels::ServerOverloadReason::AcceptQueueFull,
+                )
+                .inc();
+            self.metrics
+                .connection_error_before_handshake_count(
+                    labels::PreHandshakeError::ResourceExhausted,
+                )
+                .inc();
             return Err(
                 labels::QuicInvalidInitialPacketError::AcceptQueueOverflow.into(),
             );
@@ -310,6 +366,8 @@ where
             } else {
                 self.config.has_ipv6pktinfo
             },
+            enable_sni_metrics: self.config.enable_sni_metrics,
+            loss_rate_sample_interval: self.config.loss_rate_sample_interval,
         };
 
         let handshake_info = HandshakeInfo::new(
@@ -361,8 +419,53 @@ where
         }
 
         send_permit.send(Ok(conn));
+        self.report_accept_queue_depth();
         Ok(())
     }
+
+    /// Updates the accept queue depth gauge with the number of connections
+    /// waiting to be accepted.
+    ///
+    /// The gauge is shared by all routers, so only the change since the last
+    /// update is applied. The receiving end of the queue is handed to the
+    /// user, so dequeues are only observed the next time this is called.
+    fn report_accept_queue_depth(&mut self) {
+        let depth = (self.accept_sink.max_capacity() -
+            self.accept_sink.capacity()) as u64;
+
+        self.metrics_accept_queue_depth
+            .dec_by(self.accept_queue_depth);
+        self.metrics_accept_queue_depth.inc_by(depth);
+        self.accept_queue_depth = depth;
+    }
+
+    /// Reports the hits and misses of the crate's buffer pools since they were
+    /// last reported by any router.
+    fn report_buffer_pool_stats(&self) {
+        let stats = BufFactory::take_pool_stats();
+
+        if stats.hits > 0 {
+            self.metrics
+                .buffer_pool_hit_count()
+                .inc_by(stats.hits as u64);
+        }
+        if stats.misses > 0 {
+            self.metrics
+                .buffer_pool_miss_count()
+                .inc_by(stats.misses as u64);
+        }
+    }
+}
+
+impl<Tx, Rx, M, I> Drop for InboundPacketRouter<Tx, Rx, M, I>
+where
+    Tx: DatagramSocketSend + Send + 'static,
+    M: Metrics,
+{
+    fn drop(&mut self) {
+        self.metrics_accept_queue_depth
+            .dec_by(self.accept_queue_depth);
+    }
 }
 
 impl<Tx, Rx, M, I> InboundPacketRouter<Tx, Rx, M, I>
@@ -411,18 +514,29 @@ where
                 return self.poll_recv_from(cx);
             };
 
+            self.socket_buffer_reporter
+                .update(udp_socket, Instant::now());
             self.reusable_cmsg_space.clear();
 
             loop {
                 let iov_s = &mut [io::IoSliceMut::new(&mut self.current_buf)];
                 match udp_socket.try_io(Interest::READABLE, || {
-                    recvmsg::<SockaddrStorage>(
+                    #[cfg(feature = "perf-quic-listener-metrics")]
+                    let start = Instant::now();
+                    let res = recvmsg::<SockaddrStorage>(
                         udp_socket.as_raw_fd(),
                         iov_s,
                         Some(&mut self.reusable_cmsg_space),
                         MsgFlags::empty(),
-                    )
-                    .map_err(|x| x.into())
+                    );
+                    // Only time calls which returned a packet, so that
+                    // spurious wakeups don't skew the distribution.
+                    #[cfg(feature = "perf-quic-listener-metrics")]
+                    if res.is_ok() {
+                        self.metrics_udp_recv_syscall_duration
+                            .observe(start.elapsed().as_nanos() as u64);
+                    }
+                    res.map_err(|x| x.into())
                 }) {
                     Ok(r) => {
                         let bytes = r.bytes;
@@ -708,6 +822,9 @@ where
                     // Process any incoming connection map signals and handle them
                     self.handle_conn_map_commands();
 
+                    self.report_accept_queue_depth();
+                    self.report_buffer_pool_stats();
+
                     return Poll::Pending;
                 },
             }
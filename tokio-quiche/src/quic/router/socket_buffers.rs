@@ -0,0 +1,130 @@
+// Copyright (C) 2025, Cloudflare, Inc.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+// IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+// THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+// PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use foundations::telemetry::metrics::Gauge;
+use libc::c_int;
+use libc::c_void;
+use libc::socklen_t;
+use std::io;
+use std::os::fd::AsRawFd;
+use std::time::Duration;
+use std::time::Instant;
+
+/// `SO_MEMINFO` from `asm/socket.h`, which `libc` does not export.
+///
+/// SPARC defines its own value. The other architectures supported by Rust
+/// use the one from `asm-generic/socket.h`.
+#[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+const SO_MEMINFO: c_int = 0x0039;
+#[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))]
+const SO_MEMINFO: c_int = 55;
+
+/// Number of `u32` values returned by `SO_MEMINFO`.
+const SK_MEMINFO_VARS: usize = 9;
+
+const UPDATE_PERIOD: Duration = Duration::from_secs(1);
+
+/// [`SocketBufferReporter`] contributes the occupancy of a socket's kernel
+/// buffers to gauges that are shared by all listener sockets.
+pub(super) struct SocketBufferReporter {
+    /// Time of last update
+    last_update: Option<Instant>,
+    /// Send buffer occupancy currently added to the gauge
+    reported_send: u64,
+    /// Receive buffer occupancy currently added to the gauge
+    reported_recv: u64,
+
+    send_gauge: Gauge,
+    recv_gauge: Gauge,
+}
+
+impl SocketBufferReporter {
+    pub(super) fn new(send_gauge: Gauge, recv_gauge: Gauge) -> Self {
+        SocketBufferReporter {
+            last_update: None,
+            reported_send: 0,
+            reported_recv: 0,
+            send_gauge,
+            recv_gauge,
+        }
+    }
+
+    /// Queries the buffer occupancy of `socket` if the last update is more
+    /// than [`UPDATE_PERIOD`] old.
+    pub(super) fn update(&mut self, socket: &impl AsRawFd, now: Instant) {
+        if self
+            .last_update
+            .is_some_and(|last| now.duration_since(last) < UPDATE_PERIOD)
+        {
+            return;
+        }
+        self.last_update = Some(now);
+
+        let Ok(meminfo) = socket_meminfo(socket) else {
+            return;
+        };
+
+        let recv = meminfo[libc::SK_MEMINFO_RMEM_ALLOC as usize] as u64;
+        let send = meminfo[libc::SK_MEMINFO_WMEM_ALLOC as usize] as u64;
+
+        self.recv_gauge.dec_by(self.reported_recv);
+        self.recv_gauge.inc_by(recv);
+        self.reported_recv = recv;
+
+        self.send_gauge.dec_by(self.reported_send);
+        self.send_gauge.inc_by(send);
+        self.reported_send = send;
+    }
+}
+
+impl Drop for SocketBufferReporter {
+    fn drop(&mut self) {
+        self.recv_gauge.dec_by(self.reported_recv);
+        self.send_gauge.dec_by(self.reported_send);
+    }
+}
+
+fn socket_meminfo(socket: &impl AsRawFd) -> io::Result<[u32; SK_MEMINFO_VARS]> {
+    let mut meminfo = [0u32; SK_MEMINFO_VARS];
+    let mut len = std::mem::size_of_val(&meminfo) as socklen_t;
+
+    // SAFETY: `meminfo` is a valid buffer of `len` bytes, and `len` is a valid
+    // pointer to a `socklen_t`
+    let ret = unsafe {
+        libc::getsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            SO_MEMINFO,
+            meminfo.as_mut_ptr() as *mut c_void,
+            &mut len,
+        )
+    };
+
+    match ret {
+        0 => Ok(meminfo),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
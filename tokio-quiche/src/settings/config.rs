@@ -43,9 +43,11 @@ pub(crate) struct Config {
     pub has_gso: bool,
     pub pacing_offload: bool,
     pub enable_expensive_packet_count_metrics: bool,
+    pub enable_sni_metrics: bool,
     pub keylog_file: Option<File>,
     pub listen_backlog: usize,
     pub handshake_timeout: Option<Duration>,
+    pub loss_rate_sample_interval: Duration,
     pub has_ippktinfo: bool,
     pub has_ipv6pktinfo: bool,
 }
@@ -93,9 +95,11 @@ impl Config {
             pacing_offload: quic_settings.enable_pacing && pacing_offload,
             enable_expensive_packet_count_metrics: quic_settings
                 .enable_expensive_packet_count_metrics,
+            enable_sni_metrics: quic_settings.enable_sni_metrics,
             keylog_file,
             listen_backlog: quic_settings.listen_backlog,
             handshake_timeout: quic_settings.handshake_timeout,
+            loss_rate_sample_interval: quic_settings.loss_rate_sample_interval,
             has_ippktinfo,
             has_ipv6pktinfo,
         })
@@ -149,6 +149,14 @@ pub struct QuicSettings {
     /// peers are arbitrary eyeballs from the global Internet.
     pub enable_expensive_packet_count_metrics: bool,
 
+    /// Optionally enables the `sni_count` metric, which counts established
+    /// connections by the server name requested by the client.
+    ///
+    /// Server names are chosen by clients, so this metric can generate many
+    /// time series. Only enable it if the set of names the server accepts is
+    /// small. Defaults to `false`.
+    pub enable_sni_metrics: bool,
+
     /// Forwards [`quiche`] logs into the logging system currently used by
     /// [`foundations`]. Defaults to `false`.
     ///
@@ -170,6 +178,17 @@ pub struct QuicSettings {
     /// Defaults to 1024 connections.
     #[serde(default = "QuicSettings::default_listen_backlog")]
     pub listen_backlog: usize,
+
+    /// The interval at which the `packet_loss_rate_pct` metric samples the
+    /// loss rate of each connection, in milliseconds.
+    ///
+    /// Defaults to 5 seconds.
+    #[serde(
+        rename = "loss_rate_sample_interval_ms",
+        default = "QuicSettings::default_loss_rate_sample_interval"
+    )]
+    #[serde_as(as = "DurationMilliSeconds")]
+    pub loss_rate_sample_interval: Duration,
 }
 
 impl QuicSettings {
@@ -244,6 +263,11 @@ impl QuicSettings {
         // ~15s.
         1024
     }
+
+    #[inline]
+    pub(crate) fn default_loss_rate_sample_interval() -> Duration {
+        Duration::from_secs(5)
+    }
 }
 
 #[cfg(test)]
@@ -261,4 +285,16 @@ mod test {
         assert_eq!(quic.handshake_timeout.unwrap(), Duration::from_secs(5));
         assert_eq!(quic.max_idle_timeout.unwrap(), Duration::from_secs(7));
     }
+
+    #[test]
+    fn loss_rate_sample_interval_parses_as_milliseconds() {
+        let quic = serde_json::from_str::<QuicSettings>(r#"{}"#).unwrap();
+        assert_eq!(quic.loss_rate_sample_interval, Duration::from_secs(5));
+
+        let quic = serde_json::from_str::<QuicSettings>(
+            r#"{ "loss_rate_sample_interval_ms": 1500 }"#,
+        )
+        .unwrap();
+        assert_eq!(quic.loss_rate_sample_interval, Duration::from_millis(1500));
+    }
 }
@@ -0,0 +1,51 @@
+// Copyright (C) 2025, Cloudflare, Inc.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+// IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+// THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+// PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+// The bucket configuration is process-wide, so this runs in its own test
+// binary where no histogram has been created yet.
+
+use tokio_quiche::metrics::BucketConfigError;
+use tokio_quiche::metrics::DefaultMetrics;
+use tokio_quiche::metrics::MetricsBucketConfig;
+
+#[test]
+fn with_bucket_config() {
+    let mut unordered = MetricsBucketConfig::default();
+    unordered.rtt_micros = &[100.0, 10.0];
+    assert_eq!(
+        DefaultMetrics::with_bucket_config(unordered).err(),
+        Some(BucketConfigError::NotAscending("rtt_micros"))
+    );
+
+    let mut config = MetricsBucketConfig::default();
+    config.rtt_micros = &[10.0, 100.0];
+    assert!(DefaultMetrics::with_bucket_config(config.clone()).is_ok());
+
+    assert_eq!(
+        DefaultMetrics::with_bucket_config(config).err(),
+        Some(BucketConfigError::AlreadyInstalled)
+    );
+}
@@ -34,6 +34,7 @@ use tokio_quiche::http3::driver::OutboundFrameSender;
 use tokio_quiche::http3::driver::ServerH3Event;
 use tokio_quiche::listen;
 use tokio_quiche::metrics::DefaultMetrics;
+use tokio_quiche::metrics::Metrics;
 use tokio_quiche::quic::ConnectionHook;
 use tokio_quiche::quic::SimpleConnectionIdGenerator;
 use tokio_quiche::quiche::h3::Header;
@@ -227,6 +228,24 @@ pub fn start_server_with_settings<F, Fut>(
 where
     F: Fn(ServerH3Connection) -> Fut + Send + Clone + 'static,
     Fut: Future<Output = ()> + Send,
+{
+    start_server_with_metrics(
+        quic_settings,
+        http3_settings,
+        hook,
+        DefaultMetrics,
+        hdl,
+    )
+}
+
+pub fn start_server_with_metrics<M, F, Fut>(
+    quic_settings: QuicSettings, http3_settings: Http3Settings,
+    hook: Arc<impl ConnectionHook + Send + Sync + 'static>, metrics: M, hdl: F,
+) -> String
+where
+    M: Metrics,
+    F: Fn(ServerH3Connection) -> Fut + Send + Clone + 'static,
+    Fut: Future<Output = ()> + Send,
 {
     let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
     let url = format!("http://127.0.0.1:{}", socket.local_addr().unwrap().port());
@@ -243,14 +262,10 @@ where
 
     let params =
         ConnectionParams::new_server(quic_settings, tls_cert_settings, hooks);
-    let mut stream = listen(
-        vec![socket],
-        params,
-        SimpleConnectionIdGenerator,
-        DefaultMetrics,
-    )
-    .unwrap()
-    .remove(0);
+    let mut stream =
+        listen(vec![socket], params, SimpleConnectionIdGenerator, metrics)
+            .unwrap()
+            .remove(0);
 
     tokio::spawn(async move {
         loop {
@@ -0,0 +1,446 @@
+// Copyright (C) 2025, Cloudflare, Inc.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+// IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+// THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+// PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use crate::fixtures::*;
+use h3i_fixtures::default_headers;
+use h3i_fixtures::h3i_config;
+use h3i_fixtures::summarize_connection;
+
+use foundations::telemetry::metrics::Counter;
+use foundations::telemetry::metrics::Gauge;
+use foundations::telemetry::metrics::Histogram;
+use foundations::telemetry::metrics::TimeHistogram;
+use futures::SinkExt;
+use tokio::sync::mpsc;
+
+use h3i::actions::h3::send_headers_frame;
+use h3i::actions::h3::Action;
+use h3i::actions::h3::StreamEvent;
+use h3i::actions::h3::StreamEventType;
+use h3i::actions::h3::WaitType;
+use h3i::quiche::h3::frame::Frame;
+use h3i::quiche::h3::WireErrorCode;
+use h3i::quiche::ConnectionError;
+
+use tokio_quiche::buf_factory::BufFactory;
+use tokio_quiche::http3::driver::H3Event;
+use tokio_quiche::http3::driver::IncomingH3Headers;
+use tokio_quiche::http3::driver::OutboundFrame;
+use tokio_quiche::http3::driver::ServerH3Event;
+use tokio_quiche::metrics::labels;
+use tokio_quiche::metrics::Metrics;
+use tokio_quiche::quiche::h3::Header;
+use tokio_quiche::BoxError;
+
+/// [`Metrics`] which keep the handles of the metrics under test, so the test
+/// can inspect the values recorded by the server.
+///
+/// Labels are ignored, and all other metrics are discarded.
+#[derive(Clone)]
+struct RecordingMetrics(Arc<RecordedMetrics>);
+
+struct RecordedMetrics {
+    streams_opened: Counter,
+    streams_closed: Counter,
+    open_streams: Gauge,
+    time_to_first_byte: TimeHistogram,
+    request_count: Counter,
+    goaway_sent: Counter,
+    goaway_received: Counter,
+    pending_send_data_bytes: Gauge,
+}
+
+impl RecordingMetrics {
+    fn new() -> Self {
+        Self(Arc::new(RecordedMetrics {
+            streams_opened: Counter::default(),
+            streams_closed: Counter::default(),
+            open_streams: Gauge::default(),
+            time_to_first_byte: TimeHistogram::new(std::iter::empty()),
+            request_count: Counter::default(),
+            goaway_sent: Counter::default(),
+            goaway_received: Counter::default(),
+            pending_send_data_bytes: Gauge::default(),
+        }))
+    }
+}
+
+impl Metrics for RecordingMetrics {
+    fn connections_in_memory(&self) -> Gauge {
+        Gauge::default()
+    }
+
+    fn maximum_writable_streams(&self) -> Histogram {
+        Histogram::new(std::iter::empty())
+    }
+
+    fn handshake_time_seconds(
+        &self, _stage: labels::QuicHandshakeStage,
+    ) -> TimeHistogram {
+        TimeHistogram::new(std::iter::empty())
+    }
+
+    fn write_errors(&self, _reason: labels::QuicWriteError) -> Counter {
+        Counter::default()
+    }
+
+    fn invalid_cid_packet_count(&self, _reason: BoxError) -> Counter {
+        Counter::default()
+    }
+
+    fn accepted_initial_packet_count(&self) -> Counter {
+        Counter::default()
+    }
+
+    fn expensive_accepted_initial_packet_count(
+        &self, _peer_ip: IpAddr,
+    ) -> Counter {
+        Counter::default()
+    }
+
+    fn rejected_initial_packet_count(
+        &self, _reason: labels::QuicInvalidInitialPacketError,
+    ) -> Counter {
+        Counter::default()
+    }
+
+    fn expensive_rejected_initial_packet_count(
+        &self, _reason: labels::QuicInvalidInitialPacketError, _peer_ip: IpAddr,
+    ) -> Counter {
+        Counter::default()
+    }
+
+    fn utilized_bandwidth(&self) -> Gauge {
+        Gauge::default()
+    }
+
+    fn max_bandwidth_mbps(&self) -> Histogram {
+        Histogram::new(std::iter::empty())
+    }
+
+    fn max_loss_pct(&self) -> Histogram {
+        Histogram::new(std::iter::empty())
+    }
+
+    fn udp_drop_count(&self) -> Counter {
+        Counter::default()
+    }
+
+    fn failed_handshakes(&self, _reason: labels::HandshakeError) -> Counter {
+        Counter::default()
+    }
+
+    fn local_h3_conn_close_error_count(
+        &self, _reason: labels::H3Error,
+    ) -> Counter {
+        Counter::default()
+    }
+
+    fn local_quic_conn_close_error_count(
+        &self, _reason: labels::QuicError,
+    ) -> Counter {
+        Counter::default()
+    }
+
+    fn peer_h3_conn_close_error_count(
+        &self, _reason: labels::H3Error,
+    ) -> Counter {
+        Counter::default()
+    }
+
+    fn peer_quic_conn_close_error_count(
+        &self, _reason: labels::QuicError,
+    ) -> Counter {
+        Counter::default()
+    }
+
+    fn tokio_runtime_task_schedule_delay_histogram(
+        &self, _task: &Arc<str>,
+    ) -> TimeHistogram {
+        TimeHistogram::new(std::iter::empty())
+    }
+
+    fn tokio_runtime_task_poll_duration_histogram(
+        &self, _task: &Arc<str>,
+    ) -> TimeHistogram {
+        TimeHistogram::new(std::iter::empty())
+    }
+
+    fn tokio_runtime_task_total_poll_time_micros(
+        &self, _task: &Arc<str>,
+    ) -> Counter {
+        Counter::default()
+    }
+
+    fn streams_opened(
+        &self, _initiator: labels::StreamInitiator,
+        _direction: labels::StreamDirection,
+    ) -> Counter {
+        self.0.streams_opened.clone()
+    }
+
+    fn streams_closed(
+        &self, _initiator: labels::StreamInitiator,
+        _direction: labels::StreamDirection,
+    ) -> Counter {
+        self.0.streams_closed.clone()
+    }
+
+    fn open_streams_gauge(&self) -> Gauge {
+        self.0.open_streams.clone()
+    }
+
+    fn h3_time_to_first_byte(&self) -> TimeHistogram {
+        self.0.time_to_first_byte.clone()
+    }
+
+    fn h3_request_count(&self) -> Counter {
+        self.0.request_count.clone()
+    }
+
+    fn h3_goaway_sent_count(&self) -> Counter {
+        self.0.goaway_sent.clone()
+    }
+
+    fn h3_goaway_received_count(&self) -> Counter {
+        self.0.goaway_received.clone()
+    }
+
+    fn pending_send_data_bytes(&self) -> Gauge {
+        self.0.pending_send_data_bytes.clone()
+    }
+}
+
+/// Serves requests like [`handle_connection`] until the connection's worker
+/// has shut down, then notifies `done_tx`.
+async fn serve_until_closed(
+    mut h3_conn: ServerH3Connection, done_tx: mpsc::UnboundedSender<()>,
+) {
+    let _ =
+        serve_connection_details(&mut h3_conn.h3_controller, Default::default())
+            .await;
+
+    // The event channel closes once the driver is dropped, after it reported
+    // its final metrics.
+    let event_rx = h3_conn.h3_controller.event_receiver_mut();
+    while event_rx.recv().await.is_some() {}
+
+    let _ = done_tx.send(());
+}
+
+#[tokio::test]
+async fn test_stream_and_request_metrics() {
+    const REQ_COUNT: u64 = 3;
+
+    let metrics = RecordingMetrics::new();
+    let (done_tx, mut done_rx) = mpsc::unbounded_channel();
+
+    let url = start_server_with_metrics(
+        QuicSettings::default(),
+        Http3Settings::default(),
+        TestConnectionHook::new(),
+        metrics.clone(),
+        move |h3_conn| serve_until_closed(h3_conn, done_tx.clone()),
+    );
+
+    h3i_fixtures::request(&url, REQ_COUNT)
+        .await
+        .expect("request failed");
+    done_rx.recv().await.expect("connection wasn't served");
+
+    let recorded = &metrics.0;
+    assert_eq!(recorded.streams_opened.get(), REQ_COUNT);
+    assert_eq!(recorded.streams_closed.get(), REQ_COUNT);
+    assert_eq!(recorded.open_streams.get(), 0);
+    assert_eq!(recorded.request_count.get(), REQ_COUNT);
+    assert_eq!(recorded.time_to_first_byte.snapshot().count(), REQ_COUNT);
+    assert_eq!(recorded.pending_send_data_bytes.get(), 0);
+}
+
+#[tokio::test]
+async fn test_goaway_metrics() {
+    let metrics = RecordingMetrics::new();
+    let (done_tx, mut done_rx) = mpsc::unbounded_channel();
+
+    let url = start_server_with_metrics(
+        QuicSettings::default(),
+        Http3Settings::default(),
+        TestConnectionHook::new(),
+        metrics.clone(),
+        move |mut h3_conn| {
+            let done_tx = done_tx.clone();
+
+            async move {
+                let mut event_rx = h3_conn.h3_controller.take_event_receiver();
+
+                while let Some(frame) = event_rx.recv().await {
+                    let ServerH3Event::Core(frame) = frame;
+                    let H3Event::IncomingHeaders(headers) = frame else {
+                        continue;
+                    };
+                    let IncomingH3Headers {
+                        stream_id,
+                        headers,
+                        send,
+                        recv,
+                        ..
+                    } = headers;
+
+                    h3_conn.h3_controller.send_goaway();
+                    tokio::spawn(handle_forwarded_headers_frame(
+                        stream_id, headers, send, recv,
+                    ));
+                }
+
+                let _ = done_tx.send(());
+            }
+        },
+    );
+
+    // h3i doesn't open a control stream on its own, so open one to carry the
+    // client's GOAWAY frame.
+    let actions = vec![
+        Action::OpenUniStream {
+            stream_id: 2,
+            fin_stream: false,
+            stream_type: 0x0,
+        },
+        Action::SendFrame {
+            stream_id: 2,
+            fin_stream: false,
+            frame: Frame::Settings {
+                max_field_section_size: None,
+                qpack_max_table_capacity: None,
+                qpack_blocked_streams: None,
+                connect_protocol_enabled: None,
+                h3_datagram: None,
+                grease: None,
+                additional_settings: None,
+                raw: None,
+            },
+        },
+        send_headers_frame(0, true, default_headers()),
+        Action::FlushPackets,
+        Action::Wait {
+            wait_type: WaitType::StreamEvent(StreamEvent {
+                stream_id: 0,
+                event_type: StreamEventType::Finished,
+            }),
+        },
+        Action::SendFrame {
+            stream_id: 2,
+            fin_stream: false,
+            frame: Frame::GoAway { id: 0 },
+        },
+        Action::FlushPackets,
+    ];
+
+    let summary = summarize_connection(h3i_config(&url), actions).await;
+    assert!(h3i_fixtures::received_status_code_on_stream(
+        &summary, 0, 200
+    ));
+    done_rx.recv().await.expect("connection wasn't served");
+
+    assert_eq!(metrics.0.goaway_sent.get(), 1);
+    assert_eq!(metrics.0.goaway_received.get(), 1);
+}
+
+#[tokio::test]
+async fn test_pending_send_data_bytes_released_on_close() {
+    let metrics = RecordingMetrics::new();
+    let (done_tx, mut done_rx) = mpsc::unbounded_channel();
+
+    let url = start_server_with_metrics(
+        QuicSettings::default(),
+        Http3Settings::default(),
+        TestConnectionHook::new(),
+        metrics.clone(),
+        move |mut h3_conn| {
+            let done_tx = done_tx.clone();
+
+            async move {
+                let mut event_rx = h3_conn.h3_controller.take_event_receiver();
+
+                while let Some(frame) = event_rx.recv().await {
+                    let ServerH3Event::Core(frame) = frame;
+                    let H3Event::IncomingHeaders(headers) = frame else {
+                        continue;
+                    };
+                    let IncomingH3Headers { mut send, .. } = headers;
+
+                    // Queue more body than the client will read before it
+                    // closes the connection.
+                    tokio::spawn(async move {
+                        let _ = send
+                            .send(OutboundFrame::Headers(vec![Header::new(
+                                b":status", b"200",
+                            )]))
+                            .await;
+
+                        for _ in 0..256 {
+                            let chunk = BufFactory::buf_from_slice(&[0; 65_536]);
+                            if send
+                                .send(OutboundFrame::body(chunk, false))
+                                .await
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                    });
+                }
+
+                let _ = done_tx.send(());
+            }
+        },
+    );
+
+    let actions = vec![
+        send_headers_frame(0, true, default_headers()),
+        Action::FlushPackets,
+        Action::Wait {
+            wait_type: WaitType::StreamEvent(StreamEvent {
+                stream_id: 0,
+                event_type: StreamEventType::Headers,
+            }),
+        },
+        Action::ConnectionClose {
+            error: ConnectionError {
+                is_app: true,
+                error_code: WireErrorCode::NoError as _,
+                reason: Vec::new(),
+            },
+        },
+    ];
+
+    summarize_connection(h3i_config(&url), actions).await;
+    done_rx.recv().await.expect("connection wasn't served");
+
+    assert_eq!(metrics.0.open_streams.get(), 0);
+    assert_eq!(metrics.0.pending_send_data_bytes.get(), 0);
+}
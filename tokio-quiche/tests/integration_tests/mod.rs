@@ -44,6 +44,8 @@ use tokio_quiche::InitialQuicConnection;
 pub mod async_callbacks;
 pub mod connection_close;
 pub mod headers;
+pub mod metrics;
+pub mod stats;
 pub mod timeouts;
 
 #[tokio::test]
@@ -0,0 +1,112 @@
+// Copyright (C) 2025, Cloudflare, Inc.
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//
+//     * Redistributions in binary form must reproduce the above copyright
+//       notice, this list of conditions and the following disclaimer in the
+//       documentation and/or other materials provided with the distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+// IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+// THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+// PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+
+use std::time::Duration;
+
+use crate::fixtures::*;
+
+use futures::SinkExt;
+use tokio::sync::mpsc;
+
+use tokio_quiche::buf_factory::BufFactory;
+use tokio_quiche::http3::driver::H3Event;
+use tokio_quiche::http3::driver::IncomingH3Headers;
+use tokio_quiche::http3::driver::OutboundFrame;
+use tokio_quiche::http3::driver::ServerH3Event;
+use tokio_quiche::quiche::h3::Header;
+
+#[tokio::test]
+async fn test_snapshot_is_refreshed_while_open() {
+    let hook = TestConnectionHook::new();
+    let (snapshot_tx, mut snapshot_rx) = mpsc::unbounded_channel();
+
+    let url = start_server_with_settings(
+        QuicSettings::default(),
+        Http3Settings::default(),
+        hook,
+        move |mut h3_conn| {
+            let snapshot_tx = snapshot_tx.clone();
+
+            async move {
+                let event_rx = h3_conn.h3_controller.event_receiver_mut();
+                let mut live = None;
+
+                while let Some(frame) = event_rx.recv().await {
+                    let ServerH3Event::Core(frame) = frame;
+                    let H3Event::IncomingHeaders(headers) = frame else {
+                        continue;
+                    };
+                    let IncomingH3Headers { mut send, .. } = headers;
+
+                    send.send(OutboundFrame::Headers(vec![Header::new(
+                        b":status", b"200",
+                    )]))
+                    .await
+                    .unwrap();
+
+                    // Keep the stream open and the connection busy until the
+                    // worker has refreshed the snapshot.
+                    for _ in 0..50 {
+                        send.send(OutboundFrame::body(
+                            BufFactory::buf_from_slice(b"a"),
+                            false,
+                        ))
+                        .await
+                        .unwrap();
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+
+                        let snapshot = h3_conn.quic_connection.snapshot();
+                        if snapshot.streams_open == Some(1) {
+                            live = Some(snapshot);
+                            break;
+                        }
+                    }
+
+                    send.send(OutboundFrame::body(
+                        BufFactory::get_empty_buf(),
+                        true,
+                    ))
+                    .await
+                    .unwrap();
+                }
+
+                let _ =
+                    snapshot_tx.send((live, h3_conn.quic_connection.snapshot()));
+            }
+        },
+    );
+
+    h3i_fixtures::request(&url, 1)
+        .await
+        .expect("request failed");
+
+    let (live, closed) = snapshot_rx.recv().await.unwrap();
+    let live = live.expect("snapshot wasn't refreshed while the stream was open");
+
+    assert!(live.packets_received > 0);
+    assert!(live.bytes_sent > 0);
+    assert_eq!(closed.streams_open, Some(0));
+    assert!(closed.packets_received >= live.packets_received);
+}